@@ -1,21 +1,92 @@
 use crate::config::Config;
+use crate::discovery;
 use crate::executor::Executor;
 use crate::ollama::OllamaClient;
 use crate::output;
 use crate::prompts;
 use crate::search::WebSearch;
-use crate::websocket::{AgentMessage, PeerConnectionResult, WebSocketServer};
+use crate::storage::{MessageDirection, Storage, StorageError, StoredMessage};
+use crate::watcher::{Watcher, WorkspaceChange};
+use crate::websocket::{
+    self, AgentMessage, PeerConnectionResult, PeerInfo, WebSocketServer, DEFAULT_LIVENESS_TIMEOUT,
+};
 use crate::writer::FileWriter;
 use rand::Rng;
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::fs;
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, oneshot, RwLock};
+use tokio::time::timeout;
+use uuid::Uuid;
 
 /// Maximum messages per conversation (total across both agents)
 const MAX_CONVERSATION_MESSAGES: usize = 4;
 
+/// How often the retry loop ticks; governs how quickly reconnect backoff
+/// and liveness reaping are re-evaluated.
+const RETRY_TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Initial delay before retrying a peer address after a failed connection attempt.
+const INITIAL_RECONNECT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Upper bound a per-address reconnect backoff can grow to.
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(3600);
+
+/// How long to wait for a peer's `CodeResponse` before giving up on a delegated task.
+const CODE_REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// How often to gossip the dialed addresses of our connected peers to them.
+const PEER_LIST_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How often the event loop checks whether it's time to initiate a conversation.
+const CONVERSATION_INITIATION_TICK: Duration = Duration::from_millis(500);
+
+/// How often to ping each connected peer to measure RTT and detect a
+/// half-open or stalled session that a dropped-socket check alone would miss.
+const PING_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Consecutive ping rounds a peer can miss before we tear down its session
+/// and let the retry loop reconnect it.
+const MAX_MISSED_PINGS: u32 = 3;
+
+/// Reconnect attempts for one address before we give up on it entirely.
+const CONN_MAX_RETRIES: u32 = 10;
+
+/// How often to print a per-peer traffic report.
+const STATS_REPORT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Per-address connection state for the reconnect supervisor: whether we're
+/// currently connected, waiting to retry (with the backoff that produced the
+/// wait and how many attempts we've burned so far), or have given up after
+/// `CONN_MAX_RETRIES` failures.
+enum PeerConnState {
+    Connected,
+    Waiting { attempts: u32, next_attempt: Instant, backoff: Duration },
+    Abandoned { attempts: u32 },
+}
+
+/// Per-peer ping/pong liveness state: the sequence number of our most
+/// recent ping, when we sent it (for RTT), whether it's been answered, and
+/// how many consecutive rounds have gone unanswered.
+struct PingState {
+    seq: u64,
+    sent_at: Instant,
+    acked: bool,
+    missed: u32,
+}
+
+/// Outcome of a code-generation task, delivered to a requester once a
+/// delegated `AgentMessage::CodeResponse` arrives from the peer that ran it.
+#[derive(Debug, Clone)]
+pub struct CodeResponse {
+    pub code: String,
+    pub run_output: String,
+    pub success: bool,
+}
+
 fn sanitize_generated_code(code: &str) -> String {
     let trimmed = code.trim();
     let mut in_fence = false;
@@ -49,6 +120,7 @@ pub struct Agent {
     pub search: WebSearch,
     pub writer: FileWriter,
     pub websocket: WebSocketServer,
+    pub storage: Storage,
 
     /// Track message counts per peer conversation: peer_id -> messages sent by us
     conversation_counts: Arc<RwLock<HashMap<String, usize>>>,
@@ -58,15 +130,83 @@ pub struct Agent {
 
     /// Track which peers we've already logged as complete
     conversation_completed: Arc<RwLock<HashSet<String>>>,
+
+    /// Delegated code-generation tasks awaiting a correlated `CodeResponse`, keyed by request ID
+    code_pending_requests: Arc<RwLock<HashMap<String, oneshot::Sender<CodeResponse>>>>,
+
+    /// WebSocket addresses known to be reachable, seeded from `config.peer_addresses`
+    /// and grown by gossiped `AgentMessage::PeerList`s from connected peers
+    known_peer_addresses: Arc<RwLock<HashSet<String>>>,
+
+    /// Monotonic counter for outgoing `Ping` sequence numbers
+    next_ping_seq: Arc<AtomicU64>,
+
+    /// Keeps the workspace filesystem watch alive for as long as the agent
+    /// runs; `None` if starting it failed. See [`Watcher`].
+    _watcher: Option<Watcher>,
+
+    /// Debounced workspace-file change events, drained by the main event
+    /// loop; taken (leaving `None`) once `run` starts consuming it. `None`
+    /// from construction if the watcher itself failed to start.
+    workspace_changes: Arc<RwLock<Option<mpsc::UnboundedReceiver<WorkspaceChange>>>>,
+
+    /// Project subdirectories with an external change noticed by the
+    /// watcher that hasn't yet been accounted for by a code-generation run;
+    /// see [`Agent::run_code_task`].
+    dirty_projects: Arc<RwLock<HashSet<String>>>,
 }
 
 impl Agent {
-    pub fn new(config: Config) -> Self {
+    /// Construct an agent, opening its SQLite-backed storage and rehydrating
+    /// conversation counters from any history persisted by a prior run.
+    pub async fn new(config: Config) -> Self {
+        let config_peer_addresses = config.peer_addresses.clone();
         let ollama = OllamaClient::new(config.ollama_host.clone(), config.ollama_model.clone());
         let executor = Executor::new("./workspace".to_string());
         let search = WebSearch::new();
         let writer = FileWriter::new("./workspace".to_string());
-        let websocket = WebSocketServer::new(config.agent_id.clone(), config.agent_port);
+        let websocket = WebSocketServer::with_connection_limits(
+            config.agent_id.clone(),
+            config.agent_port,
+            config.incoming_channel_capacity,
+            config.max_inbound,
+            config.max_outbound,
+            config.reserved_only,
+            config.reserved_peers.clone(),
+        );
+
+        let storage = Storage::open(config.storage_path.clone())
+            .await
+            .unwrap_or_else(|e| panic!("Failed to open storage at {}: {}", config.storage_path, e));
+
+        let counters = storage.load_counters().await.unwrap_or_else(|e| {
+            output::agent_warn(
+                &config.agent_id,
+                &format!("Failed to rehydrate conversation history: {}", e),
+            );
+            Default::default()
+        });
+
+        // The watcher needs the directory to already exist; project
+        // subdirectories are created lazily by `Executor::cargo_new`, but
+        // the workspace root itself isn't guaranteed to exist yet.
+        if let Err(e) = fs::create_dir_all("./workspace").await {
+            output::agent_warn(
+                &config.agent_id,
+                &format!("Failed to create workspace directory: {}", e),
+            );
+        }
+
+        let (watcher, workspace_changes) = match Watcher::watch("./workspace") {
+            Ok((watcher, rx)) => (Some(watcher), Some(rx)),
+            Err(e) => {
+                output::agent_warn(
+                    &config.agent_id,
+                    &format!("Failed to start workspace watcher: {}", e),
+                );
+                (None, None)
+            }
+        };
 
         Self {
             config,
@@ -75,18 +215,34 @@ impl Agent {
             search,
             writer,
             websocket,
-            conversation_counts: Arc::new(RwLock::new(HashMap::new())),
-            conversation_received_counts: Arc::new(RwLock::new(HashMap::new())),
-            conversation_completed: Arc::new(RwLock::new(HashSet::new())),
+            storage,
+            conversation_counts: Arc::new(RwLock::new(counters.sent)),
+            conversation_received_counts: Arc::new(RwLock::new(counters.received)),
+            conversation_completed: Arc::new(RwLock::new(counters.completed)),
+            code_pending_requests: Arc::new(RwLock::new(HashMap::new())),
+            known_peer_addresses: Arc::new(RwLock::new(
+                config_peer_addresses.into_iter().collect(),
+            )),
+            next_ping_seq: Arc::new(AtomicU64::new(1)),
+            _watcher: watcher,
+            workspace_changes: Arc::new(RwLock::new(workspace_changes)),
+            dirty_projects: Arc::new(RwLock::new(HashSet::new())),
         }
     }
 
+    /// Take the workspace-change receiver (can only be called once), mirroring
+    /// [`WebSocketServer::take_incoming_receiver`].
+    async fn take_workspace_changes(&self) -> Option<mpsc::UnboundedReceiver<WorkspaceChange>> {
+        self.workspace_changes.write().await.take()
+    }
+
     async fn maybe_log_conversation_complete(
         agent_id: &str,
         peer_id: &str,
         our_count: usize,
         recv_count: usize,
         conversation_completed: &Arc<RwLock<HashSet<String>>>,
+        storage: &Storage,
     ) {
         let limit = MAX_CONVERSATION_MESSAGES / 2;
         if our_count < limit || recv_count < limit {
@@ -95,6 +251,12 @@ impl Agent {
 
         let mut completed = conversation_completed.write().await;
         if completed.insert(peer_id.to_string()) {
+            if let Err(e) = storage.mark_conversation_complete(peer_id).await {
+                output::agent_warn(
+                    agent_id,
+                    &format!("Failed to persist conversation completion: {}", e),
+                );
+            }
             output::agent_info(
                 agent_id,
                 &format!(
@@ -105,6 +267,164 @@ impl Agent {
         }
     }
 
+    /// Run the generate -> sanitize -> write -> build/execute pipeline for
+    /// `prompt` against `project_dir`, logging each step. Shared by the
+    /// agent's own startup code generation and by delegated `CodeRequest`s.
+    async fn run_code_task(&self, prompt: &str, project_dir: &str) -> CodeResponse {
+        // If the watcher noticed an external edit to this project since we
+        // last touched it, re-read the file it's about to overwrite and log
+        // what we found instead of silently clobbering it with generated code.
+        if self.dirty_projects.write().await.remove(project_dir) {
+            let main_path = format!("{}/src/main.rs", project_dir);
+            match self.writer.read_file(&main_path).await {
+                Ok(content) => output::agent_warn(
+                    &self.config.agent_id,
+                    &format!(
+                        "{} changed on disk since the last run ({} bytes); regenerating anyway",
+                        main_path,
+                        content.len()
+                    ),
+                ),
+                Err(_) => output::agent_warn(
+                    &self.config.agent_id,
+                    &format!("{} changed on disk since the last run", main_path),
+                ),
+            }
+        }
+
+        Self::execute_code_task(
+            &self.ollama,
+            &self.executor,
+            &self.writer,
+            &self.config.agent_id,
+            prompt,
+            project_dir,
+        )
+        .await
+    }
+
+    /// Free-standing form of [`Agent::run_code_task`] that takes its
+    /// collaborators by reference instead of `&self`, so it can also run
+    /// inside the spawned task handling incoming peer messages.
+    async fn execute_code_task(
+        ollama: &OllamaClient,
+        executor: &Executor,
+        writer: &FileWriter,
+        agent_id: &str,
+        prompt: &str,
+        project_dir: &str,
+    ) -> CodeResponse {
+        let code = match ollama.generate(prompt).await {
+            Ok(code) => sanitize_generated_code(&code),
+            Err(e) => {
+                output::agent_error(agent_id, &format!("Code generation failed: {}", e));
+                return CodeResponse {
+                    code: String::new(),
+                    run_output: String::new(),
+                    success: false,
+                };
+            }
+        };
+
+        let cargo_toml = Path::new("./workspace")
+            .join(project_dir)
+            .join("Cargo.toml");
+
+        if fs::metadata(&cargo_toml).await.is_err() {
+            match executor.cargo_new(project_dir).await {
+                Ok(message) => output::agent_success(agent_id, &message),
+                Err(e) => {
+                    output::agent_error(agent_id, &format!("Project creation failed: {}", e))
+                }
+            }
+        }
+
+        let main_path = format!("{}/src/main.rs", project_dir);
+        let path = match writer.write_file(&main_path, &code).await {
+            Ok(path) => path,
+            Err(e) => {
+                output::agent_error(agent_id, &format!("File write failed: {}", e));
+                return CodeResponse {
+                    code,
+                    run_output: String::new(),
+                    success: false,
+                };
+            }
+        };
+        output::agent_success(agent_id, &format!("Code written to: {}", path));
+        output::code_block(agent_id, &code);
+
+        match executor.cargo_run(project_dir).await {
+            Ok(run_output) => {
+                output::agent_success(agent_id, "Code executed successfully");
+                let trimmed = run_output.trim();
+                if !trimmed.is_empty() {
+                    output::agent_info(agent_id, &format!("Program output: {}", trimmed));
+                }
+                CodeResponse {
+                    code,
+                    run_output,
+                    success: true,
+                }
+            }
+            Err(e) => {
+                output::agent_error(agent_id, &format!("Code execution failed: {}", e));
+                CodeResponse {
+                    code,
+                    run_output: e.to_string(),
+                    success: false,
+                }
+            }
+        }
+    }
+
+    /// Delegate a code-generation task to `target_agent` and await its
+    /// `CodeResponse`, giving up and logging via `output::agent_warn` after
+    /// `CODE_REQUEST_TIMEOUT`.
+    pub async fn request_code(
+        &self,
+        target_agent: &str,
+        prompt: String,
+        project_dir: String,
+    ) -> Option<CodeResponse> {
+        let request_id = Uuid::new_v4().to_string();
+        let (resp_tx, resp_rx) = oneshot::channel();
+        self.code_pending_requests
+            .write()
+            .await
+            .insert(request_id.clone(), resp_tx);
+
+        self.websocket
+            .broadcast(AgentMessage::CodeRequest {
+                request_id: request_id.clone(),
+                agent_id: self.config.agent_id.clone(),
+                target_agent: target_agent.to_string(),
+                prompt,
+                project_dir,
+            })
+            .await;
+
+        match timeout(CODE_REQUEST_TIMEOUT, resp_rx).await {
+            Ok(Ok(response)) => Some(response),
+            Ok(Err(_)) | Err(_) => {
+                self.code_pending_requests.write().await.remove(&request_id);
+                output::agent_warn(
+                    &self.config.agent_id,
+                    &format!(
+                        "Code request {} to {} timed out",
+                        request_id, target_agent
+                    ),
+                );
+                None
+            }
+        }
+    }
+
+    /// Fetch a peer's full persisted conversation history, oldest first.
+    pub async fn peer_transcript(&self, peer_id: &str) -> Result<Vec<StoredMessage>, StorageError> {
+        self.storage.transcript(peer_id).await
+    }
+
     pub async fn run(&self) {
         output::startup_banner(&self.config.agent_id);
 
@@ -192,64 +512,8 @@ impl Agent {
         // Generate code before starting the WebSocket server
         if self.config.ollama_enabled && ollama_ready {
             output::section("Code Generation");
-            match self.ollama.generate(prompts::CODE_PROMPT_ADD).await {
-                Ok(code) => {
-                    let code = sanitize_generated_code(&code);
-                    let project_dir = "generated_add";
-                    let cargo_toml = Path::new("./workspace")
-                        .join(project_dir)
-                        .join("Cargo.toml");
-
-                    if fs::metadata(&cargo_toml).await.is_err() {
-                        match self.executor.cargo_new(project_dir).await {
-                            Ok(message) => output::agent_success(&self.config.agent_id, &message),
-                            Err(e) => output::agent_error(
-                                &self.config.agent_id,
-                                &format!("Project creation failed: {}", e),
-                            ),
-                        }
-                    }
-
-                    let main_path = format!("{}/src/main.rs", project_dir);
-                    match self.writer.write_file(&main_path, &code).await {
-                        Ok(path) => {
-                            output::agent_success(
-                                &self.config.agent_id,
-                                &format!("Code written to: {}", path),
-                            );
-                            output::code_block(&self.config.agent_id, &code);
-
-                            match self.executor.cargo_run(project_dir).await {
-                                Ok(output) => {
-                                    output::agent_success(
-                                        &self.config.agent_id,
-                                        "Code executed successfully",
-                                    );
-                                    let trimmed = output.trim();
-                                    if !trimmed.is_empty() {
-                                        output::agent_info(
-                                            &self.config.agent_id,
-                                            &format!("Program output: {}", trimmed),
-                                        );
-                                    }
-                                }
-                                Err(e) => output::agent_error(
-                                    &self.config.agent_id,
-                                    &format!("Code execution failed: {}", e),
-                                ),
-                            }
-                        }
-                        Err(e) => output::agent_error(
-                            &self.config.agent_id,
-                            &format!("File write failed: {}", e),
-                        ),
-                    }
-                }
-                Err(e) => output::agent_error(
-                    &self.config.agent_id,
-                    &format!("Code generation failed: {}", e),
-                ),
-            }
+            self.run_code_task(prompts::CODE_PROMPT_ADD, "generated_add")
+                .await;
         } else if self.config.ollama_enabled {
             output::agent_warn(
                 &self.config.agent_id,
@@ -265,9 +529,26 @@ impl Agent {
         // Start WebSocket server
         self.websocket.start().await;
 
+        // Also listen on a Unix domain socket for local peers, if configured
+        if let Some(path) = &self.config.unix_socket_path {
+            self.websocket.start_unix(path).await;
+        }
+
         // Give the server a moment to start
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 
+        // Start LAN peer discovery, if enabled
+        if self.config.discovery_enabled {
+            output::agent_status(&self.config.agent_id, "Starting LAN peer discovery...");
+            tokio::spawn(discovery::start(
+                self.config.agent_id.clone(),
+                self.config.agent_port,
+                self.config.discovery_group.clone(),
+                Duration::from_secs(self.config.discovery_interval_secs),
+                self.websocket.clone(),
+            ));
+        }
+
         // Connect to peer agents
         let mut connected_count = 0;
         let mut failed_count = 0;
@@ -288,9 +569,14 @@ impl Agent {
                 ),
             );
 
-            for peer in &self.config.peer_addresses {
-                match self.websocket.connect_to_peer(peer).await {
-                    PeerConnectionResult::Connected(_) => {
+            let results = self
+                .websocket
+                .connect_to_peers(&self.config.peer_addresses)
+                .await;
+
+            for result in results {
+                match result {
+                    PeerConnectionResult::Connected(_, _) => {
                         connected_count += 1;
                     }
                     PeerConnectionResult::Failed(url, reason) => {
@@ -320,6 +606,10 @@ impl Agent {
                     ),
                 );
             }
+
+            if connected_count > 0 {
+                self.broadcast_peer_list().await;
+            }
         }
 
         // Give time for any incoming connections to complete handshake
@@ -327,290 +617,619 @@ impl Agent {
 
         // Start conversation handler
         output::section("Agent Communication");
+        output::agent_ready(&self.config.agent_id, self.websocket.peer_count().await);
+
+        if !self.websocket.has_peers().await {
+            output::agent_info(
+                &self.config.agent_id,
+                "No peers connected, waiting for connections...",
+            );
+        }
+
+        // Steady state: a single select-driven loop over every event source
+        // (incoming peer messages, connection retry/gossip ticks, the
+        // conversation-initiation check, ping and stats ticks, and workspace
+        // file changes), each branch mutating shared agent
+        // state and returning so the loop can re-select. This replaces three
+        // copies of the same "should I initiate?" logic with one.
+        let mut incoming_rx = self.websocket.take_incoming_receiver().await;
+        let mut workspace_changes = self.take_workspace_changes().await;
+        let mut initiated_conversation = false;
+        let mut reconnect_state: HashMap<String, PeerConnState> = HashMap::new();
+        let mut ping_state: HashMap<String, PingState> = HashMap::new();
+        let mut next_peer_list_broadcast = Instant::now() + PEER_LIST_INTERVAL;
+
+        let mut retry_tick = tokio::time::interval(RETRY_TICK_INTERVAL);
+        let mut initiation_tick = tokio::time::interval(CONVERSATION_INITIATION_TICK);
+        let mut ping_tick = tokio::time::interval(PING_INTERVAL);
+        let mut stats_tick = tokio::time::interval(STATS_REPORT_INTERVAL);
 
-        // Take the incoming message receiver and spawn handler
-        if let Some(mut incoming_rx) = self.websocket.take_incoming_receiver().await {
-            let agent_id = self.config.agent_id.clone();
-            let ollama = self.ollama.clone();
-            let ollama_enabled = self.config.ollama_enabled;
-            let websocket = self.websocket.clone();
-            let conversation_counts = self.conversation_counts.clone();
-            let conversation_received_counts = self.conversation_received_counts.clone();
-            let conversation_completed = self.conversation_completed.clone();
-
-            tokio::spawn(async move {
-                while let Some(msg) = incoming_rx.recv().await {
+        loop {
+            tokio::select! {
+                msg = async { incoming_rx.as_mut().unwrap().recv().await }, if incoming_rx.is_some() => {
                     match msg {
-                        AgentMessage::Text {
-                            agent_id: peer_id,
-                            content,
-                        } => {
-                            output::peer_recv_text(&peer_id, content.trim_matches('"'));
-                            let recv_count = {
-                                let mut counts = conversation_received_counts.write().await;
+                        Some(msg) => self.handle_incoming_message(msg, &mut ping_state).await,
+                        None => incoming_rx = None,
+                    }
+                }
+                _ = retry_tick.tick() => {
+                    self.on_retry_tick(&mut reconnect_state, &mut next_peer_list_broadcast).await;
+                }
+                _ = initiation_tick.tick(), if !initiated_conversation => {
+                    initiated_conversation = self.maybe_initiate_conversation().await;
+                }
+                _ = ping_tick.tick() => {
+                    self.on_ping_tick(&mut ping_state).await;
+                }
+                _ = stats_tick.tick() => {
+                    self.on_stats_tick(&reconnect_state).await;
+                }
+                change = async { workspace_changes.as_mut().unwrap().recv().await }, if workspace_changes.is_some() => {
+                    match change {
+                        Some(change) => self.handle_workspace_change(change).await,
+                        None => workspace_changes = None,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Note an externally-made change to a workspace file so the next
+    /// code-generation run against its project re-reads the file instead of
+    /// blindly overwriting it with stale generated content.
+    async fn handle_workspace_change(&self, change: WorkspaceChange) {
+        output::agent_info(
+            &self.config.agent_id,
+            &format!(
+                "Noticed external {:?} of {} (project {})",
+                change.kind,
+                change.path.display(),
+                change.agent_id
+            ),
+        );
+        self.dirty_projects.write().await.insert(change.agent_id);
+    }
+
+    /// Handle one message drained from the incoming-message channel.
+    async fn handle_incoming_message(&self, msg: AgentMessage, ping_state: &mut HashMap<String, PingState>) {
+        match msg {
+            AgentMessage::Text {
+                origin: peer_id,
+                content,
+                ..
+            } => {
+                output::peer_recv_text(&peer_id, content.trim_matches('"'));
+                if let Err(e) = self
+                    .storage
+                    .record_message(&peer_id, MessageDirection::Received, &content)
+                    .await
+                {
+                    output::agent_warn(
+                        &self.config.agent_id,
+                        &format!("Failed to persist received message: {}", e),
+                    );
+                }
+                let recv_count = {
+                    let mut counts = self.conversation_received_counts.write().await;
+                    let entry = counts.entry(peer_id.clone()).or_insert(0);
+                    *entry += 1;
+                    *entry
+                };
+
+                // Check if we should respond (limit conversation length)
+                let our_count = {
+                    let counts = self.conversation_counts.read().await;
+                    counts.get(&peer_id).copied().unwrap_or(0)
+                };
+
+                // Each agent sends at most 2 messages (4 total in conversation)
+                if our_count >= MAX_CONVERSATION_MESSAGES / 2 {
+                    Agent::maybe_log_conversation_complete(
+                        &self.config.agent_id,
+                        &peer_id,
+                        our_count,
+                        recv_count,
+                        &self.conversation_completed,
+                        &self.storage,
+                    )
+                    .await;
+                    return;
+                }
+
+                // Generate a response if Ollama is available
+                if self.config.ollama_enabled && self.ollama.is_available().await {
+                    let prompt =
+                        prompts::peer_response_prompt(&self.config.agent_id, &peer_id, &content);
+
+                    match self.ollama.generate(&prompt).await {
+                        Ok(response) => {
+                            let response = response.trim().to_string();
+
+                            // Update our message count
+                            let new_count = {
+                                let mut counts = self.conversation_counts.write().await;
                                 let entry = counts.entry(peer_id.clone()).or_insert(0);
                                 *entry += 1;
                                 *entry
                             };
 
-                            // Check if we should respond (limit conversation length)
-                            let our_count = {
-                                let counts = conversation_counts.read().await;
-                                counts.get(&peer_id).copied().unwrap_or(0)
-                            };
-
-                            // Each agent sends at most 2 messages (4 total in conversation)
-                            if our_count >= MAX_CONVERSATION_MESSAGES / 2 {
-                                Agent::maybe_log_conversation_complete(
-                                    &agent_id,
-                                    &peer_id,
-                                    our_count,
-                                    recv_count,
-                                    &conversation_completed,
-                                )
-                                .await;
-                                continue;
+                            output::peer_send_text(&self.config.agent_id, &response);
+                            if let Err(e) = self
+                                .storage
+                                .record_message(&peer_id, MessageDirection::Sent, &response)
+                                .await
+                            {
+                                output::agent_warn(
+                                    &self.config.agent_id,
+                                    &format!("Failed to persist sent message: {}", e),
+                                );
                             }
+                            self.websocket
+                                .broadcast(AgentMessage::text(
+                                    self.config.agent_id.clone(),
+                                    response,
+                                ))
+                                .await;
 
-                            // Generate a response if Ollama is available
-                            if ollama_enabled && ollama.is_available().await {
-                                let prompt =
-                                    prompts::peer_response_prompt(&agent_id, &peer_id, &content);
-
-                                match ollama.generate(&prompt).await {
-                                    Ok(response) => {
-                                        let response = response.trim().to_string();
-
-                                        // Update our message count
-                                        let new_count = {
-                                            let mut counts = conversation_counts.write().await;
-                                            let entry = counts.entry(peer_id.clone()).or_insert(0);
-                                            *entry += 1;
-                                            *entry
-                                        };
-
-                                        output::peer_send_text(&agent_id, &response);
-                                        websocket
-                                            .broadcast(AgentMessage::Text {
-                                                agent_id: agent_id.clone(),
-                                                content: response,
-                                            })
-                                            .await;
-
-                                        Agent::maybe_log_conversation_complete(
-                                            &agent_id,
-                                            &peer_id,
-                                            new_count,
-                                            recv_count,
-                                            &conversation_completed,
-                                        )
-                                        .await;
-                                    }
-                                    Err(e) => {
-                                        output::agent_error(
-                                            &agent_id,
-                                            &format!("Failed to generate response: {}", e),
-                                        );
-                                    }
-                                }
-                            }
+                            Agent::maybe_log_conversation_complete(
+                                &self.config.agent_id,
+                                &peer_id,
+                                new_count,
+                                recv_count,
+                                &self.conversation_completed,
+                                &self.storage,
+                            )
+                            .await;
                         }
-                        AgentMessage::Number { agent_id, value } => {
-                            output::peer_recv_number(&agent_id, value);
+                        Err(e) => {
+                            output::agent_error(
+                                &self.config.agent_id,
+                                &format!("Failed to generate response: {}", e),
+                            );
                         }
-                        _ => {}
                     }
                 }
-            });
+            }
+            AgentMessage::Number { origin, value, .. } => {
+                output::peer_recv_number(&origin, value);
+            }
+            AgentMessage::Request {
+                request_id,
+                target_agent,
+                payload,
+                ..
+            } => {
+                if target_agent != self.config.agent_id {
+                    return;
+                }
+                if self.config.ollama_enabled && self.ollama.is_available().await {
+                    match self.ollama.generate(&payload).await {
+                        Ok(response) => {
+                            self.websocket.respond(request_id, response.trim().to_string()).await;
+                        }
+                        Err(e) => {
+                            self.websocket.respond_error(request_id, e.to_string()).await;
+                        }
+                    }
+                } else {
+                    self.websocket
+                        .respond_error(request_id, "Ollama not available".to_string())
+                        .await;
+                }
+            }
+            AgentMessage::CodeRequest {
+                request_id,
+                target_agent,
+                prompt,
+                project_dir,
+                ..
+            } => {
+                if target_agent != self.config.agent_id {
+                    return;
+                }
+                let response = self.run_code_task(&prompt, &project_dir).await;
+                self.websocket
+                    .broadcast(AgentMessage::CodeResponse {
+                        request_id,
+                        agent_id: self.config.agent_id.clone(),
+                        code: response.code,
+                        run_output: response.run_output,
+                        success: response.success,
+                    })
+                    .await;
+            }
+            AgentMessage::CodeResponse {
+                request_id,
+                code,
+                run_output,
+                success,
+                ..
+            } => {
+                if let Some(resp_tx) = self.code_pending_requests.write().await.remove(&request_id)
+                {
+                    let _ = resp_tx.send(CodeResponse {
+                        code,
+                        run_output,
+                        success,
+                    });
+                }
+            }
+            AgentMessage::PeerList {
+                agent_id: sender_id,
+                known_peers,
+            } => {
+                let mut known = self.known_peer_addresses.write().await;
+                for peer in known_peers {
+                    // Never dial ourselves, e.g. if our own address gets
+                    // echoed back to us via a neighbor's peer list.
+                    if peer.agent_id == self.config.agent_id {
+                        continue;
+                    }
+                    // `sender_id` is directly connected to us, and advertised
+                    // `peer.agent_id` as one of its own peers, so it's a
+                    // next hop we can route through to reach it.
+                    self.websocket.record_route(&peer.agent_id, &sender_id).await;
+                    known.insert(peer.address);
+                }
+            }
+            AgentMessage::Ping {
+                agent_id: peer_id,
+                seq,
+                peer_list_hash,
+            } => {
+                if peer_list_hash != self.known_peers_hash().await {
+                    self.broadcast_peer_list().await;
+                }
+                // Reply only to whoever sent this Ping - broadcasting the
+                // Pong would let an unrelated peer's in-flight ping_state
+                // entry match this seq and record a bogus RTT.
+                self.websocket
+                    .send_to_peer(
+                        &peer_id,
+                        AgentMessage::Pong {
+                            agent_id: self.config.agent_id.clone(),
+                            seq,
+                            peer_list_hash: self.known_peers_hash().await,
+                        },
+                    )
+                    .await;
+            }
+            AgentMessage::Pong {
+                agent_id: peer_id,
+                seq,
+                peer_list_hash,
+            } => {
+                if peer_list_hash != self.known_peers_hash().await {
+                    self.broadcast_peer_list().await;
+                }
+                if let Some(state) = ping_state.get_mut(&peer_id) {
+                    if state.seq == seq && !state.acked {
+                        state.acked = true;
+                        let rtt = state.sent_at.elapsed();
+                        self.websocket.record_rtt(&peer_id, rtt).await;
+                        output::agent_info(
+                            &self.config.agent_id,
+                            &format!("Pong from {} (seq {}), rtt {:?}", peer_id, seq, rtt),
+                        );
+                    }
+                }
+            }
+            _ => {}
         }
+    }
 
-        // Only initiate conversation if we have the "lower" agent ID (to avoid both starting)
-        // This ensures exactly one agent starts the conversation
-        let has_peers = self.websocket.has_peers().await;
-        let peers = self.websocket.get_peer_ids().await;
+    /// Ping every connected peer, tearing down any that missed
+    /// `MAX_MISSED_PINGS` consecutive rounds so the retry loop reconnects it.
+    async fn on_ping_tick(&self, ping_state: &mut HashMap<String, PingState>) {
+        let connected = self.websocket.get_peer_ids().await;
+        if connected.is_empty() {
+            return;
+        }
+
+        let seq = self.next_ping_seq.fetch_add(1, Ordering::SeqCst);
+        for peer in &connected {
+            let missed = match ping_state.get(peer) {
+                Some(prev) if !prev.acked => prev.missed + 1,
+                _ => 0,
+            };
+
+            if missed >= MAX_MISSED_PINGS {
+                ping_state.remove(peer);
+                if self.websocket.disconnect_peer(peer).await {
+                    output::agent_warn(
+                        &self.config.agent_id,
+                        &format!("Peer {} missed {} consecutive pings, tearing down connection", peer, missed),
+                    );
+                }
+                continue;
+            }
 
-        if has_peers {
-            let should_initiate = peers.iter().all(|peer| self.config.agent_id < *peer);
+            ping_state.insert(
+                peer.clone(),
+                PingState {
+                    seq,
+                    sent_at: Instant::now(),
+                    acked: false,
+                    missed,
+                },
+            );
+        }
 
-            if should_initiate && self.config.ollama_enabled && self.ollama.is_available().await {
-                output::agent_status(
-                    &self.config.agent_id,
-                    "Initiating conversation with peers...",
-                );
+        self.websocket
+            .broadcast(AgentMessage::Ping {
+                agent_id: self.config.agent_id.clone(),
+                seq,
+                peer_list_hash: self.known_peers_hash().await,
+            })
+            .await;
+    }
 
-                match self.ollama.generate(prompts::PEER_GREETING_PROMPT).await {
-                    Ok(greeting) => {
-                        let greeting = greeting.trim().to_string();
+    /// Stable hash of the `PeerList` we'd currently gossip (see
+    /// [`Agent::broadcast_peer_list`]), piggybacked on `Ping`/`Pong` so a
+    /// peer whose hash differs from ours can be reactively sent a fresh
+    /// dump instead of waiting for the next `PEER_LIST_INTERVAL` tick.
+    async fn known_peers_hash(&self) -> u64 {
+        let known_peers: Vec<PeerInfo> = self
+            .websocket
+            .peer_urls()
+            .await
+            .into_iter()
+            .map(|(agent_id, address)| PeerInfo { agent_id, address })
+            .collect();
+        websocket::peer_list_hash(&known_peers)
+    }
 
-                        // Count this as our first message to all peers
-                        {
-                            let mut counts = self.conversation_counts.write().await;
-                            for peer in &peers {
-                                *counts.entry(peer.clone()).or_insert(0) += 1;
-                            }
+    /// Gossip our connected peers' addresses, reap stale peers, and retry dialing
+    /// any known address we're not currently connected to.
+    async fn on_retry_tick(
+        &self,
+        reconnect_state: &mut HashMap<String, PeerConnState>,
+        next_peer_list_broadcast: &mut Instant,
+    ) {
+        // Share what we know of the mesh with our peers, so a node that
+        // bootstrapped from a single seed address eventually learns of every
+        // other node, one hop at a time
+        if Instant::now() >= *next_peer_list_broadcast {
+            *next_peer_list_broadcast = Instant::now() + PEER_LIST_INTERVAL;
+            self.broadcast_peer_list().await;
+        }
+
+        for peer_id in self.websocket.reap_stale_peers(DEFAULT_LIVENESS_TIMEOUT).await {
+            output::agent_warn(
+                &self.config.agent_id,
+                &format!("Peer {} timed out, no messages received", peer_id),
+            );
+        }
+
+        // Retry connecting to any peers we're not connected to, honoring
+        // each address's backoff so a dead peer isn't redialed every tick.
+        // This covers both statically configured addresses and ones learned
+        // at runtime via gossiped `PeerList`s.
+        let known_addresses: Vec<String> =
+            self.known_peer_addresses.read().await.iter().cloned().collect();
+        for peer in &known_addresses {
+            if self.websocket.is_connected_to_url(peer).await {
+                reconnect_state.insert(peer.clone(), PeerConnState::Connected);
+                continue;
+            }
+
+            if let Some(PeerConnState::Abandoned { .. }) = reconnect_state.get(peer) {
+                continue;
+            }
+
+            let now = Instant::now();
+            let due = match reconnect_state.get(peer) {
+                Some(PeerConnState::Waiting { next_attempt, .. }) => now >= *next_attempt,
+                _ => true,
+            };
+            if !due {
+                continue;
+            }
+
+            output::agent_status(
+                &self.config.agent_id,
+                &format!("Retrying connection to {}...", peer),
+            );
+            match self.websocket.connect_to_peer(peer).await {
+                PeerConnectionResult::Connected(_, direction) => {
+                    output::agent_success(
+                        &self.config.agent_id,
+                        &format!("Successfully connected to {} ({:?})", peer, direction),
+                    );
+                    reconnect_state.insert(peer.clone(), PeerConnState::Connected);
+                    self.broadcast_peer_list().await;
+                }
+                PeerConnectionResult::Failed(_, _) => {
+                    // Already logged in connect_to_peer
+                    let (attempts, backoff) = match reconnect_state.get(peer) {
+                        Some(PeerConnState::Waiting { attempts, backoff, .. }) => {
+                            (*attempts + 1, (*backoff * 2).min(MAX_RECONNECT_BACKOFF))
                         }
+                        _ => (1, INITIAL_RECONNECT_BACKOFF),
+                    };
 
-                        output::agent_success(
-                            &self.config.agent_id,
-                            &format!("Starting conversation: \"{}\"", greeting),
-                        );
-                        output::peer_send_text(&self.config.agent_id, &greeting);
-                        self.websocket
-                            .broadcast(AgentMessage::Text {
-                                agent_id: self.config.agent_id.clone(),
-                                content: greeting,
-                            })
-                            .await;
-                    }
-                    Err(e) => {
+                    if attempts >= CONN_MAX_RETRIES {
                         output::agent_warn(
                             &self.config.agent_id,
-                            &format!("Failed to generate greeting: {}", e),
+                            &format!("Giving up on {} after {} failed attempts", peer, attempts),
+                        );
+                        reconnect_state.insert(peer.clone(), PeerConnState::Abandoned { attempts });
+                    } else {
+                        reconnect_state.insert(
+                            peer.clone(),
+                            PeerConnState::Waiting { attempts, next_attempt: now + backoff, backoff },
                         );
                     }
                 }
-            } else if !should_initiate {
-                output::agent_info(
-                    &self.config.agent_id,
-                    "Waiting for peer to initiate conversation...",
-                );
-            } else if !self.config.ollama_enabled {
-                self.send_random_number().await;
             }
-        } else {
-            output::agent_info(
-                &self.config.agent_id,
-                "No peers connected, waiting for connections...",
-            );
         }
+    }
 
-        output::agent_ready(&self.config.agent_id, self.websocket.peer_count().await);
+    /// Tell every connected peer which peers we're dialed into, so a node
+    /// that bootstrapped from a single seed address can have its neighbors'
+    /// neighbors added to its own retry path without being configured with
+    /// every address up front.
+    async fn broadcast_peer_list(&self) {
+        if !self.websocket.has_peers().await {
+            return;
+        }
 
-        // Quick poll for peers if we don't have any yet (check every 500ms for 5 seconds)
-        let mut initiated_conversation = has_peers;
-        if !initiated_conversation {
-            for _ in 0..10 {
-                tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+        let known_peers: Vec<PeerInfo> = self
+            .websocket
+            .peer_urls()
+            .await
+            .into_iter()
+            .map(|(agent_id, address)| PeerInfo { agent_id, address })
+            .collect();
 
-                if self.websocket.has_peers().await {
-                    let peers = self.websocket.get_peer_ids().await;
-                    let should_initiate = peers.iter().all(|peer| self.config.agent_id < *peer);
+        if known_peers.is_empty() {
+            return;
+        }
 
-                    if should_initiate
-                        && self.config.ollama_enabled
-                        && self.ollama.is_available().await
-                    {
-                        initiated_conversation = true;
-                        output::peer_event(
-                            &self.config.agent_id,
-                            "Peers connected! Starting conversation...",
-                        );
+        self.websocket
+            .broadcast(AgentMessage::PeerList {
+                agent_id: self.config.agent_id.clone(),
+                known_peers,
+            })
+            .await;
+    }
 
-                        if let Ok(greeting) =
-                            self.ollama.generate(prompts::PEER_GREETING_PROMPT).await
-                        {
-                            let greeting = greeting.trim().to_string();
+    /// Print a report of per-peer traffic (messages/bytes sent and
+    /// received), last-seen age, and retry/backoff state for any address
+    /// we're still trying to reconnect, so users can diagnose flaky links
+    /// and asymmetric traffic in the mesh.
+    async fn on_stats_tick(&self, reconnect_state: &HashMap<String, PeerConnState>) {
+        let peer_stats = self.websocket.peer_stats().await;
+        let last_seen = self.websocket.last_seen_snapshot().await;
 
-                            {
-                                let mut counts = self.conversation_counts.write().await;
-                                for peer in &peers {
-                                    *counts.entry(peer.clone()).or_insert(0) += 1;
-                                }
-                            }
+        if peer_stats.is_empty() && reconnect_state.is_empty() {
+            return;
+        }
 
-                            output::agent_success(
-                                &self.config.agent_id,
-                                &format!("Starting conversation: \"{}\"", greeting),
-                            );
-                            output::peer_send_text(&self.config.agent_id, &greeting);
-                            self.websocket
-                                .broadcast(AgentMessage::Text {
-                                    agent_id: self.config.agent_id.clone(),
-                                    content: greeting,
-                                })
-                                .await;
-                        }
-                        break;
-                    } else if !should_initiate {
-                        // The other agent will initiate
-                        initiated_conversation = true;
-                        break;
-                    }
+        output::section("Peer Traffic");
+        for (peer_id, stats) in &peer_stats {
+            let sent_messages: u64 = stats.sent.values().map(|s| s.messages).sum();
+            let sent_bytes: u64 = stats.sent.values().map(|s| s.bytes).sum();
+            let received_messages: u64 = stats.received.values().map(|s| s.messages).sum();
+            let received_bytes: u64 = stats.received.values().map(|s| s.bytes).sum();
+            let last_seen_desc = last_seen
+                .get(peer_id)
+                .map(|seen| format!("{}s ago", seen.elapsed().as_secs()))
+                .unwrap_or_else(|| "never".to_string());
+
+            let ping_desc = match (stats.avg_ping(), stats.med_ping(), stats.max_ping()) {
+                (Some(avg), Some(med), Some(max)) => {
+                    format!(", ping avg {:?}/med {:?}/max {:?}", avg, med, max)
                 }
-            }
+                _ => String::new(),
+            };
+
+            output::config_item(
+                &self.config.agent_id,
+                peer_id,
+                &format!(
+                    "sent {} msgs/{} bytes, recv {} msgs/{} bytes, connects {}, disconnects {}, last seen {}{}",
+                    sent_messages,
+                    sent_bytes,
+                    received_messages,
+                    received_bytes,
+                    stats.connects,
+                    stats.disconnects,
+                    last_seen_desc,
+                    ping_desc
+                ),
+            );
         }
 
-        // Keep the agent running and periodically retry peer connections
-        let retry_interval = tokio::time::Duration::from_secs(10);
+        for (address, state) in reconnect_state {
+            let desc = match state {
+                PeerConnState::Connected => continue,
+                PeerConnState::Waiting { attempts, next_attempt, .. } => format!(
+                    "retrying, {} attempt(s) so far, next in {:?}",
+                    attempts,
+                    next_attempt.saturating_duration_since(Instant::now())
+                ),
+                PeerConnState::Abandoned { attempts } => {
+                    format!("abandoned after {} failed attempts", attempts)
+                }
+            };
+            output::config_item(&self.config.agent_id, address, &desc);
+        }
+    }
 
-        loop {
-            tokio::time::sleep(retry_interval).await;
+    /// Check whether we should kick off the peer conversation and, if so, do
+    /// it. Returns `true` once there's nothing further for this agent to
+    /// decide (either it sent the greeting, deferred to the peer, or fell
+    /// back to sending a random number), so the caller can stop ticking.
+    async fn maybe_initiate_conversation(&self) -> bool {
+        if !self.websocket.has_peers().await {
+            return false;
+        }
 
-            // Check if we should initiate now (if we have new peers and haven't initiated yet)
-            if !initiated_conversation && self.websocket.has_peers().await {
-                let peers = self.websocket.get_peer_ids().await;
-                let should_initiate = peers.iter().all(|peer| self.config.agent_id < *peer);
+        let peers = self.websocket.get_peer_ids().await;
+        let should_initiate = peers.iter().all(|peer| self.config.agent_id < *peer);
 
-                if should_initiate && self.config.ollama_enabled && self.ollama.is_available().await
-                {
-                    initiated_conversation = true;
-                    output::peer_event(
-                        &self.config.agent_id,
-                        "Peers connected! Starting conversation...",
-                    );
+        if !should_initiate {
+            output::agent_info(
+                &self.config.agent_id,
+                "Waiting for peer to initiate conversation...",
+            );
+            return true;
+        }
 
-                    if let Ok(greeting) = self.ollama.generate(prompts::PEER_GREETING_PROMPT).await
-                    {
-                        let greeting = greeting.trim().to_string();
+        if !self.config.ollama_enabled {
+            self.send_random_number().await;
+            return true;
+        }
 
-                        {
-                            let mut counts = self.conversation_counts.write().await;
-                            for peer in &peers {
-                                *counts.entry(peer.clone()).or_insert(0) += 1;
-                            }
-                        }
+        if !self.ollama.is_available().await {
+            return false;
+        }
 
-                        output::agent_success(
-                            &self.config.agent_id,
-                            &format!("Starting conversation: \"{}\"", greeting),
-                        );
-                        output::peer_send_text(&self.config.agent_id, &greeting);
-                        self.websocket
-                            .broadcast(AgentMessage::Text {
-                                agent_id: self.config.agent_id.clone(),
-                                content: greeting,
-                            })
-                            .await;
-                    }
-                }
-            }
+        output::peer_event(
+            &self.config.agent_id,
+            "Peers connected! Starting conversation...",
+        );
 
-            // Retry connecting to any peers we're not connected to
-            if !self.config.peer_addresses.is_empty() {
-                let connected = self.websocket.peer_count().await;
-                if connected >= self.config.peer_addresses.len() {
-                    continue;
+        match self.ollama.generate(prompts::PEER_GREETING_PROMPT).await {
+            Ok(greeting) => {
+                let greeting = greeting.trim().to_string();
+
+                {
+                    let mut counts = self.conversation_counts.write().await;
+                    for peer in &peers {
+                        *counts.entry(peer.clone()).or_insert(0) += 1;
+                    }
                 }
 
-                for peer in &self.config.peer_addresses {
-                    if !self.websocket.is_connected_to_url(peer).await {
-                        output::agent_status(
+                output::agent_success(
+                    &self.config.agent_id,
+                    &format!("Starting conversation: \"{}\"", greeting),
+                );
+                output::peer_send_text(&self.config.agent_id, &greeting);
+                for peer in &peers {
+                    if let Err(e) = self
+                        .storage
+                        .record_message(peer, MessageDirection::Sent, &greeting)
+                        .await
+                    {
+                        output::agent_warn(
                             &self.config.agent_id,
-                            &format!("Retrying connection to {}...", peer),
+                            &format!("Failed to persist sent message: {}", e),
                         );
-                        match self.websocket.connect_to_peer(peer).await {
-                            PeerConnectionResult::Connected(_) => {
-                                output::agent_success(
-                                    &self.config.agent_id,
-                                    &format!("Successfully connected to {}", peer),
-                                );
-                            }
-                            PeerConnectionResult::Failed(_, _) => {
-                                // Already logged in connect_to_peer
-                            }
-                        }
                     }
                 }
+                self.websocket
+                    .broadcast(AgentMessage::text(self.config.agent_id.clone(), greeting))
+                    .await;
+                true
+            }
+            Err(e) => {
+                output::agent_warn(
+                    &self.config.agent_id,
+                    &format!("Failed to generate greeting: {}", e),
+                );
+                false
             }
         }
     }
@@ -623,10 +1242,7 @@ impl Agent {
         );
         output::peer_send_number(&self.config.agent_id, value);
         self.websocket
-            .broadcast(AgentMessage::Number {
-                agent_id: self.config.agent_id.clone(),
-                value,
-            })
+            .broadcast(AgentMessage::number(self.config.agent_id.clone(), value))
             .await;
     }
 }
@@ -10,6 +10,65 @@ use unicode_width::UnicodeWidthChar;
 
 const RESET: &str = "\x1b[0m";
 
+/// Strip everything from an untrusted raw log line except tabs, printable
+/// characters, and well-formed CSI/SGR (color/style) escape sequences.
+/// Cursor-movement, OSC, and other control sequences are dropped outright,
+/// so a peer's raw log output can't inject terminal control sequences into
+/// the rendered card. Mirrors `output::sanitize_ansi`, duplicated here since
+/// this binary doesn't otherwise depend on the agent's output module.
+fn sanitize_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\t' => out.push(c),
+            '\u{1b}' if chars.peek() == Some(&'[') => {
+                chars.next();
+                let mut params = String::new();
+                let mut final_byte = None;
+                for next in chars.by_ref() {
+                    if ('\u{20}'..='\u{3f}').contains(&next) {
+                        params.push(next);
+                    } else {
+                        final_byte = Some(next);
+                        break;
+                    }
+                }
+                let is_sgr = params.chars().all(|p| p.is_ascii_digit() || p == ';');
+                if final_byte == Some('m') && is_sgr {
+                    out.push('\u{1b}');
+                    out.push('[');
+                    out.push_str(&params);
+                    out.push('m');
+                }
+            }
+            '\u{1b}' if chars.peek() == Some(&']') => {
+                chars.next();
+                // OSC sequence - consume through its BEL or ST terminator and drop it
+                for next in chars.by_ref() {
+                    if next == '\u{7}' {
+                        break;
+                    }
+                    if next == '\u{1b}' {
+                        if chars.peek() == Some(&'\\') {
+                            chars.next();
+                        }
+                        break;
+                    }
+                }
+            }
+            '\u{1b}' => {
+                // Unrecognized escape introducer - drop just it, not what follows
+            }
+            _ if c.is_control() => {} // drop other control chars (\r, bell, backspace, etc.)
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
 fn parse_arg(arg: &str) -> Option<(String, String)> {
     if let Some((left, right)) = arg.split_once('=') {
         return Some((left.to_string(), right.to_string()));
@@ -48,7 +107,7 @@ fn main() -> io::Result<()> {
             for line in reader.lines() {
                 match line {
                     Ok(line) => {
-                        if tx.send((agent_id.clone(), line)).is_err() {
+                        if tx.send((agent_id.clone(), sanitize_ansi(&line))).is_err() {
                             break;
                         }
                     }
@@ -174,7 +233,7 @@ fn wrap_line_words(line: &str, width: usize) -> Vec<String> {
     let mut chunks = Vec::new();
     let mut current = String::new();
     let mut current_width = 0usize;
-    let mut active_sgr = String::new();
+    let mut active = AnsiState::default();
 
     let mut word = String::new();
     let mut word_width = 0usize;
@@ -243,12 +302,13 @@ fn wrap_line_words(line: &str, width: usize) -> Vec<String> {
                     }
                 }
             }
-            update_sgr_state(&seq, &mut active_sgr);
+            active.apply(&seq);
             word.push_str(&seq);
             continue;
         }
 
         if ch.is_whitespace() {
+            let active_sgr = active.prefix();
             flush_word(
                 &mut current,
                 &mut current_width,
@@ -278,7 +338,7 @@ fn wrap_line_words(line: &str, width: usize) -> Vec<String> {
     flush_word(
         &mut current,
         &mut current_width,
-        &active_sgr,
+        &active.prefix(),
         &mut word,
         &mut word_width,
         &mut word_has_text,
@@ -331,15 +391,104 @@ fn split_word(word: &str, width: usize) -> Vec<String> {
     parts
 }
 
-fn update_sgr_state(seq: &str, active: &mut String) {
-    if !seq.ends_with('m') {
-        return;
+/// Tracks which SGR attributes are currently active so a styled line split
+/// across multiple wrapped card rows can re-emit the combined set of active
+/// attributes on each continuation row. A single cached raw escape sequence
+/// isn't enough for this: bold set by one sequence and a foreground color
+/// set by a later, separate sequence need to both survive onto the next row,
+/// not have the second silently overwrite the first.
+#[derive(Default, Clone)]
+struct AnsiState {
+    bold: bool,
+    underline: bool,
+    fg: Option<String>,
+    bg: Option<String>,
+}
+
+impl AnsiState {
+    /// Fold one `ESC[...m` sequence's parameters into the running state.
+    fn apply(&mut self, seq: &str) {
+        if !seq.ends_with('m') {
+            return;
+        }
+        let body = seq.trim_start_matches("\x1b[").trim_end_matches('m');
+        let params: Vec<&str> = if body.is_empty() {
+            vec!["0"]
+        } else {
+            body.split(';').collect()
+        };
+
+        let mut i = 0;
+        while i < params.len() {
+            match params[i] {
+                "0" => *self = AnsiState::default(),
+                "1" => self.bold = true,
+                "22" => self.bold = false,
+                "4" => self.underline = true,
+                "24" => self.underline = false,
+                "39" => self.fg = None,
+                "49" => self.bg = None,
+                code @ ("38" | "48") => {
+                    // Extended color: `38;5;N` (256-color) or `38;2;R;G;B` (truecolor).
+                    let mut extended = vec![code];
+                    if let Some(&mode) = params.get(i + 1) {
+                        extended.push(mode);
+                        let take = match mode {
+                            "2" => 3,
+                            "5" => 1,
+                            _ => 0,
+                        };
+                        for part in params.iter().skip(i + 2).take(take) {
+                            extended.push(part);
+                        }
+                        i += 1 + take;
+                    }
+                    let value = Some(extended.join(";"));
+                    if code == "38" {
+                        self.fg = value;
+                    } else {
+                        self.bg = value;
+                    }
+                }
+                code => {
+                    if let Ok(n) = code.parse::<u16>() {
+                        match n {
+                            30..=37 | 90..=97 => self.fg = Some(code.to_string()),
+                            40..=47 | 100..=107 => self.bg = Some(code.to_string()),
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            i += 1;
+        }
+    }
+
+    /// Whether any attribute is active, i.e. whether a continuation row
+    /// needs a re-anchoring prefix at all.
+    fn is_empty(&self) -> bool {
+        !self.bold && !self.underline && self.fg.is_none() && self.bg.is_none()
     }
-    let seq_body = seq.trim_start_matches("\x1b[").trim_end_matches('m');
-    if seq_body.is_empty() || seq_body.split(';').any(|part| part == "0") {
-        active.clear();
-    } else {
-        *active = seq.to_string();
+
+    /// Render the combined state back as a single `ESC[...m` sequence.
+    fn prefix(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        let mut codes = Vec::new();
+        if self.bold {
+            codes.push("1".to_string());
+        }
+        if self.underline {
+            codes.push("4".to_string());
+        }
+        if let Some(fg) = &self.fg {
+            codes.push(fg.clone());
+        }
+        if let Some(bg) = &self.bg {
+            codes.push(bg.clone());
+        }
+        format!("\x1b[{}m", codes.join(";"))
     }
 }
 
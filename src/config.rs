@@ -9,6 +9,23 @@ pub struct Config {
     pub ollama_model: String,
     pub direction: String,
     pub peer_addresses: Vec<String>,
+    pub discovery_enabled: bool,
+    pub discovery_group: String,
+    pub discovery_interval_secs: u64,
+    pub incoming_channel_capacity: usize,
+    pub storage_path: String,
+    /// Cap on concurrently accepted inbound connections
+    pub max_inbound: usize,
+    /// Cap on concurrently dialed outbound connections
+    pub max_outbound: usize,
+    /// When set, inbound connections are only accepted from `reserved_peers`
+    pub reserved_only: bool,
+    /// Agent IDs allowed to connect when `reserved_only` is set, and always
+    /// prioritized over non-reserved peers when inbound slots are full
+    pub reserved_peers: Vec<String>,
+    /// When set, also bind a Unix-domain-socket listener at this filesystem
+    /// path, so local peers can connect without going through loopback TCP
+    pub unix_socket_path: Option<String>,
 }
 
 impl Config {
@@ -35,6 +52,40 @@ impl Config {
             ollama_model: env::var("OLLAMA_MODEL").unwrap_or_else(|_| "gpt-oss:20b".to_string()),
             direction: env::var("DIRECTION").unwrap_or_else(|_| "roam".to_string()),
             peer_addresses,
+            discovery_enabled: env::var("DISCOVERY_ENABLED")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase()
+                == "true",
+            discovery_group: env::var("DISCOVERY_GROUP")
+                .unwrap_or_else(|_| "239.255.0.1:9999".to_string()),
+            discovery_interval_secs: env::var("DISCOVERY_INTERVAL")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .unwrap_or(5),
+            incoming_channel_capacity: env::var("INCOMING_CHANNEL_CAPACITY")
+                .unwrap_or_else(|_| "64".to_string())
+                .parse()
+                .unwrap_or(64),
+            storage_path: env::var("STORAGE_PATH").unwrap_or_else(|_| "./agent.db".to_string()),
+            max_inbound: env::var("MAX_INBOUND")
+                .unwrap_or_else(|_| "32".to_string())
+                .parse()
+                .unwrap_or(32),
+            max_outbound: env::var("MAX_OUTBOUND")
+                .unwrap_or_else(|_| "32".to_string())
+                .parse()
+                .unwrap_or(32),
+            reserved_only: env::var("RESERVED_ONLY")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase()
+                == "true",
+            reserved_peers: env::var("RESERVED_PEERS")
+                .unwrap_or_default()
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            unix_socket_path: env::var("UNIX_SOCKET_PATH").ok(),
         }
     }
 }
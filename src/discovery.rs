@@ -0,0 +1,129 @@
+use crate::output;
+use crate::websocket::{PeerConnectionResult, WebSocketServer};
+use serde::{Deserialize, Serialize};
+use std::net::{Ipv4Addr, SocketAddrV4};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::UdpSocket;
+
+/// Advertised over the multicast group so peers know who we are and where to
+/// reach our WebSocket server. The advertising host's IP is taken from the
+/// UDP packet's source address rather than embedded here, since that's the
+/// address peers can actually route to.
+#[derive(Debug, Serialize, Deserialize)]
+struct Beacon {
+    agent_id: String,
+    port: u16,
+}
+
+/// Start broadcasting and listening for LAN discovery beacons on `group`
+/// (an IPv4 multicast address in `host:port` form), feeding any
+/// newly-discovered peer's advertised address into `websocket`'s
+/// `connect_to_peer`. Runs until the process exits; intended to be spawned
+/// as a background task.
+pub async fn start(
+    agent_id: String,
+    ws_port: u16,
+    group: String,
+    interval: Duration,
+    websocket: WebSocketServer,
+) {
+    let group_addr: SocketAddrV4 = match group.parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            output::agent_warn(&agent_id, &format!("Invalid DISCOVERY_GROUP {}: {}", group, e));
+            return;
+        }
+    };
+
+    let socket = match UdpSocket::bind((Ipv4Addr::UNSPECIFIED, group_addr.port())).await {
+        Ok(socket) => socket,
+        Err(e) => {
+            output::agent_warn(&agent_id, &format!("Discovery disabled, failed to bind: {}", e));
+            return;
+        }
+    };
+
+    if let Err(e) = socket.join_multicast_v4(*group_addr.ip(), Ipv4Addr::UNSPECIFIED) {
+        output::agent_warn(
+            &agent_id,
+            &format!("Discovery disabled, failed to join multicast group: {}", e),
+        );
+        return;
+    }
+
+    output::agent_success(
+        &agent_id,
+        &format!("LAN peer discovery enabled on {}", group_addr),
+    );
+
+    let socket = Arc::new(socket);
+    tokio::spawn(beacon_loop(
+        socket.clone(),
+        group_addr,
+        agent_id.clone(),
+        ws_port,
+        interval,
+    ));
+    listen_loop(socket, agent_id, websocket).await;
+}
+
+/// Periodically re-announce this agent on the multicast group.
+async fn beacon_loop(
+    socket: Arc<UdpSocket>,
+    target: SocketAddrV4,
+    agent_id: String,
+    ws_port: u16,
+    interval: Duration,
+) {
+    let payload = serde_json::to_vec(&Beacon {
+        agent_id,
+        port: ws_port,
+    })
+    .unwrap();
+
+    loop {
+        let _ = socket.send_to(&payload, target).await;
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Listen for beacons from other agents and dial any we're not already connected to.
+async fn listen_loop(socket: Arc<UdpSocket>, agent_id: String, websocket: WebSocketServer) {
+    let mut buf = [0u8; 512];
+
+    loop {
+        let (len, from) = match socket.recv_from(&mut buf).await {
+            Ok(pair) => pair,
+            Err(_) => continue,
+        };
+
+        let Ok(beacon) = serde_json::from_slice::<Beacon>(&buf[..len]) else {
+            continue;
+        };
+
+        if beacon.agent_id == agent_id {
+            continue;
+        }
+
+        let peer_url = format!("ws://{}:{}/ws", from.ip(), beacon.port);
+        if websocket.is_connected_to_url(&peer_url).await {
+            continue;
+        }
+
+        output::peer_event(
+            &agent_id,
+            &format!("Discovered peer {} via multicast beacon", beacon.agent_id),
+        );
+
+        match websocket.connect_to_peer(&peer_url).await {
+            PeerConnectionResult::Connected(_, _) => {}
+            PeerConnectionResult::Failed(_, reason) => {
+                output::agent_warn(
+                    &agent_id,
+                    &format!("Discovery connect to {} failed: {}", peer_url, reason),
+                );
+            }
+        }
+    }
+}
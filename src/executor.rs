@@ -1,7 +1,12 @@
+use crate::writer::{Edit, FileWriter, WriterError};
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::Path;
-use std::process::Output;
+use std::process::{Output, Stdio};
 use thiserror::Error;
+use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
+use tokio::sync::mpsc;
 
 #[derive(Error, Debug)]
 pub enum ExecutorError {
@@ -9,8 +14,147 @@ pub enum ExecutorError {
     Io(#[from] std::io::Error),
     #[error("Command failed with status {status}: {stderr}")]
     CommandFailed { status: i32, stderr: String },
+    #[error("Failed to apply suggested fix: {0}")]
+    Writer(#[from] WriterError),
 }
 
+/// Severity of a cargo compiler diagnostic, mapped from its `message.level`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+    Other,
+}
+
+impl Severity {
+    fn from_level(level: &str) -> Self {
+        match level {
+            "error" => Severity::Error,
+            "warning" => Severity::Warning,
+            "note" => Severity::Note,
+            _ => Severity::Other,
+        }
+    }
+}
+
+/// A single cargo compiler diagnostic, flattened from a `compiler-message`
+/// record's primary span so downstream agents can react programmatically
+/// (count errors, jump to a file/line) instead of scraping rendered text.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub file: Option<String>,
+    pub line_start: Option<usize>,
+    pub column_start: Option<usize>,
+    pub rendered: String,
+}
+
+#[derive(Deserialize)]
+struct CargoMessage {
+    reason: String,
+    message: Option<CompilerMessage>,
+}
+
+#[derive(Deserialize)]
+struct CompilerMessage {
+    message: String,
+    level: String,
+    spans: Vec<Span>,
+    #[serde(default)]
+    children: Vec<CompilerMessage>,
+    rendered: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Span {
+    file_name: String,
+    line_start: usize,
+    column_start: usize,
+    is_primary: bool,
+    byte_start: usize,
+    byte_end: usize,
+    #[serde(default)]
+    suggestion_applicability: Option<String>,
+    #[serde(default)]
+    suggested_replacement: Option<String>,
+}
+
+/// Parse cargo's newline-delimited JSON message stream, keeping only
+/// `compiler-message` records and flattening each one's primary span.
+fn parse_diagnostics(stdout: &[u8]) -> Vec<Diagnostic> {
+    String::from_utf8_lossy(stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<CargoMessage>(line).ok())
+        .filter(|msg| msg.reason == "compiler-message")
+        .filter_map(|msg| msg.message)
+        .map(|compiler_message| {
+            let primary_span = compiler_message.spans.iter().find(|span| span.is_primary);
+            Diagnostic {
+                severity: Severity::from_level(&compiler_message.level),
+                message: compiler_message.message,
+                file: primary_span.map(|span| span.file_name.clone()),
+                line_start: primary_span.map(|span| span.line_start),
+                column_start: primary_span.map(|span| span.column_start),
+                rendered: compiler_message.rendered.unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+/// One machine-applicable compiler suggestion, flattened to the byte range
+/// in `file` it replaces.
+struct SuggestedFix {
+    file: String,
+    byte_start: usize,
+    byte_end: usize,
+    replacement: String,
+}
+
+/// Summary of an [`Executor::apply_suggested_fixes`] run.
+#[derive(Debug, Clone, Default)]
+pub struct FixSummary {
+    pub files_touched: Vec<String>,
+    pub edits_applied: usize,
+}
+
+/// Walk a `compiler-message` record (and its children, e.g. "try this")
+/// collecting every span whose suggestion is `MachineApplicable`.
+fn collect_fixes_from_message(message: &CompilerMessage) -> Vec<SuggestedFix> {
+    let mut fixes: Vec<SuggestedFix> = message
+        .spans
+        .iter()
+        .filter(|span| span.suggestion_applicability.as_deref() == Some("MachineApplicable"))
+        .filter_map(|span| {
+            span.suggested_replacement.clone().map(|replacement| SuggestedFix {
+                file: span.file_name.clone(),
+                byte_start: span.byte_start,
+                byte_end: span.byte_end,
+                replacement,
+            })
+        })
+        .collect();
+
+    for child in &message.children {
+        fixes.extend(collect_fixes_from_message(child));
+    }
+
+    fixes
+}
+
+/// Parse cargo's JSON message stream for every machine-applicable suggested fix.
+fn collect_suggested_fixes(stdout: &[u8]) -> Vec<SuggestedFix> {
+    String::from_utf8_lossy(stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<CargoMessage>(line).ok())
+        .filter(|msg| msg.reason == "compiler-message")
+        .filter_map(|msg| msg.message)
+        .flat_map(|compiler_message| collect_fixes_from_message(&compiler_message))
+        .collect()
+}
+
+#[derive(Clone)]
 pub struct Executor {
     workspace: String,
 }
@@ -86,4 +230,150 @@ impl Executor {
             })
         }
     }
+
+    /// Run `cargo build` asking for structured JSON diagnostics instead of
+    /// plain text, returning every parsed `compiler-message` alongside
+    /// whether the build succeeded. Unlike [`Executor::cargo_build`], a
+    /// failing build isn't an `ExecutorError` here - the diagnostics
+    /// themselves carry the errors, so the caller can inspect them.
+    pub async fn cargo_build_diagnostics(
+        &self,
+        project_dir: &str,
+    ) -> Result<(Vec<Diagnostic>, bool), ExecutorError> {
+        self.run_cargo_diagnostics(&["build"], project_dir).await
+    }
+
+    /// `cargo test` counterpart to [`Executor::cargo_build_diagnostics`].
+    pub async fn cargo_test_diagnostics(
+        &self,
+        project_dir: &str,
+    ) -> Result<(Vec<Diagnostic>, bool), ExecutorError> {
+        self.run_cargo_diagnostics(&["test"], project_dir).await
+    }
+
+    async fn run_cargo_diagnostics(
+        &self,
+        args: &[&str],
+        project_dir: &str,
+    ) -> Result<(Vec<Diagnostic>, bool), ExecutorError> {
+        let (stdout, success) = self.run_cargo_json(args, project_dir).await?;
+        Ok((parse_diagnostics(&stdout), success))
+    }
+
+    async fn run_cargo_json(
+        &self,
+        args: &[&str],
+        project_dir: &str,
+    ) -> Result<(Vec<u8>, bool), ExecutorError> {
+        let path = Path::new(&self.workspace).join(project_dir);
+        let mut full_args: Vec<&str> = args.to_vec();
+        full_args.push("--message-format=json-diagnostic-rendered-ansi");
+
+        let output = Command::new("cargo")
+            .args(&full_args)
+            .current_dir(&path)
+            .output()
+            .await?;
+
+        Ok((output.stdout, output.status.success()))
+    }
+
+    /// Streaming counterpart to [`Executor::cargo_build_diagnostics`]: forwards
+    /// every stdout/stderr line to `sink` as cargo produces it, so a caller
+    /// (the output module, or a FIFO feeding `log_mux`) can render live
+    /// progress instead of waiting for the whole build to finish, while the
+    /// full stdout is still accumulated so the final diagnostics can be
+    /// parsed once the process exits.
+    pub async fn cargo_build_streaming(
+        &self,
+        project_dir: &str,
+        sink: mpsc::UnboundedSender<String>,
+    ) -> Result<(Vec<Diagnostic>, bool), ExecutorError> {
+        let (stdout, success) = self.run_cargo_streaming(&["build"], project_dir, sink).await?;
+        Ok((parse_diagnostics(&stdout), success))
+    }
+
+    async fn run_cargo_streaming(
+        &self,
+        args: &[&str],
+        project_dir: &str,
+        sink: mpsc::UnboundedSender<String>,
+    ) -> Result<(Vec<u8>, bool), ExecutorError> {
+        let path = Path::new(&self.workspace).join(project_dir);
+        let mut full_args: Vec<&str> = args.to_vec();
+        full_args.push("--message-format=json-diagnostic-rendered-ansi");
+
+        let mut child = Command::new("cargo")
+            .args(&full_args)
+            .current_dir(&path)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let stdout = child.stdout.take().expect("stdout was piped");
+        let stderr = child.stderr.take().expect("stderr was piped");
+
+        let stdout_sink = sink.clone();
+        let stdout_task = tokio::spawn(async move {
+            let mut lines = BufReader::new(stdout).lines();
+            let mut accumulated = Vec::new();
+            while let Some(line) = lines.next_line().await? {
+                accumulated.extend_from_slice(line.as_bytes());
+                accumulated.push(b'\n');
+                let _ = stdout_sink.send(line);
+            }
+            Ok::<Vec<u8>, std::io::Error>(accumulated)
+        });
+
+        let stderr_task = tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Some(line) = lines.next_line().await.ok().flatten() {
+                let _ = sink.send(line);
+            }
+        });
+
+        let status = child.wait().await?;
+        let accumulated = stdout_task
+            .await
+            .map_err(|e| ExecutorError::Io(std::io::Error::other(e)))??;
+        let _ = stderr_task.await;
+
+        Ok((accumulated, status.success()))
+    }
+
+    /// Run a build, collect every machine-applicable fix the compiler
+    /// suggested, and apply them directly to the workspace files via
+    /// `FileWriter::apply_edits` - a built-in `cargo fix`-style autofix loop
+    /// driven by the compiler's own suggestions. Edits are grouped per file
+    /// and handed to `apply_edits` together, which rejects out-of-range or
+    /// overlapping ranges and backs up the file before mutating it.
+    pub async fn apply_suggested_fixes(&self, project_dir: &str) -> Result<FixSummary, ExecutorError> {
+        let (stdout, _success) = self.run_cargo_json(&["build"], project_dir).await?;
+        let fixes = collect_suggested_fixes(&stdout);
+
+        let mut by_file: HashMap<String, Vec<SuggestedFix>> = HashMap::new();
+        for fix in fixes {
+            by_file.entry(fix.file.clone()).or_default().push(fix);
+        }
+
+        let writer = FileWriter::new(format!("{}/{}", self.workspace, project_dir));
+        let mut summary = FixSummary::default();
+
+        for (file, fixes) in by_file {
+            let edits: Vec<Edit> = fixes
+                .iter()
+                .map(|fix| Edit {
+                    range: fix.byte_start..fix.byte_end,
+                    replacement: fix.replacement.clone(),
+                })
+                .collect();
+
+            writer.apply_edits(&file, &edits).await?;
+
+            summary.edits_applied += edits.len();
+            summary.files_touched.push(file);
+        }
+
+        Ok(summary)
+    }
 }
@@ -1,8 +1,11 @@
 mod agent;
 mod config;
+mod discovery;
 mod executor;
 mod ollama;
 mod search;
+mod storage;
+mod watcher;
 mod writer;
 
 use agent::Agent;
@@ -12,6 +15,6 @@ use config::Config;
 async fn main() {
     dotenvy::dotenv().ok();
     let config = Config::from_env();
-    let agent = Agent::new(config);
+    let agent = Agent::new(config).await;
     agent.run().await;
 }
@@ -1,4 +1,5 @@
-use reqwest::Client;
+use futures_util::{stream, Stream, StreamExt};
+use reqwest::{Client, Response};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -23,6 +24,15 @@ pub struct GenerateResponse {
     pub done: bool,
 }
 
+/// Drives `generate_stream`'s line-at-a-time NDJSON parsing: accumulates
+/// response bytes until a newline completes a `GenerateResponse`, then
+/// hands back the token and carries any leftover bytes to the next poll.
+enum GenerateStreamState {
+    Active { response: Response, buffer: Vec<u8> },
+    Failed(Option<OllamaError>),
+    Done,
+}
+
 #[derive(Clone)]
 pub struct OllamaClient {
     client: Client,
@@ -40,23 +50,83 @@ impl OllamaClient {
     }
 
     pub async fn generate(&self, prompt: &str) -> Result<String, OllamaError> {
+        let mut tokens = Box::pin(self.generate_stream(prompt).await);
+        let mut output = String::new();
+        while let Some(token) = tokens.next().await {
+            output.push_str(&token?);
+        }
+        Ok(output)
+    }
+
+    /// Streams the completion token by token as Ollama emits it, instead of
+    /// waiting for the full response. Reads the NDJSON body chunk by chunk,
+    /// yielding each line's `response` field and ending the stream once a
+    /// line with `done: true` arrives (or the request itself fails).
+    pub async fn generate_stream(
+        &self,
+        prompt: &str,
+    ) -> impl Stream<Item = Result<String, OllamaError>> {
         let url = format!("{}/api/generate", self.host);
         let request = GenerateRequest {
             model: &self.model,
             prompt,
-            stream: false,
+            stream: true,
         };
 
-        let response = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?
-            .json::<GenerateResponse>()
-            .await?;
+        let state = match self.client.post(&url).json(&request).send().await {
+            Ok(response) => GenerateStreamState::Active {
+                response,
+                buffer: Vec::new(),
+            },
+            Err(e) => GenerateStreamState::Failed(Some(e.into())),
+        };
+
+        stream::unfold(state, |mut state| async move {
+            loop {
+                match state {
+                    GenerateStreamState::Failed(err) => {
+                        let err = err?;
+                        return Some((Err(err), GenerateStreamState::Failed(None)));
+                    }
+                    GenerateStreamState::Done => return None,
+                    GenerateStreamState::Active {
+                        mut response,
+                        mut buffer,
+                    } => {
+                        if let Some(pos) = buffer.iter().position(|&b| b == b'\n') {
+                            let line: Vec<u8> = buffer.drain(..=pos).collect();
+                            let line = &line[..line.len() - 1];
+
+                            if line.is_empty() {
+                                state = GenerateStreamState::Active { response, buffer };
+                                continue;
+                            }
+
+                            return match serde_json::from_slice::<GenerateResponse>(line) {
+                                Ok(parsed) => {
+                                    let next = if parsed.done {
+                                        GenerateStreamState::Done
+                                    } else {
+                                        GenerateStreamState::Active { response, buffer }
+                                    };
+                                    Some((Ok(parsed.response), next))
+                                }
+                                Err(e) => Some((Err(e.into()), GenerateStreamState::Done)),
+                            };
+                        }
 
-        Ok(response.response)
+                        match response.chunk().await {
+                            Ok(Some(chunk)) => {
+                                buffer.extend_from_slice(&chunk);
+                                state = GenerateStreamState::Active { response, buffer };
+                            }
+                            Ok(None) => state = GenerateStreamState::Done,
+                            Err(e) => return Some((Err(e.into()), GenerateStreamState::Done)),
+                        }
+                    }
+                }
+            }
+        })
     }
 
     pub async fn is_available(&self) -> bool {
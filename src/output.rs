@@ -3,8 +3,13 @@
 use owo_colors::OwoColorize;
 use std::collections::HashSet;
 use std::env;
+use std::io::IsTerminal;
 use std::sync::Mutex;
 use std::sync::OnceLock;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::ThemeSet;
+use syntect::parsing::SyntaxSet;
+use syntect::util::as_24_bit_terminal_escaped;
 
 /// Robot emoji prefix for all agent output
 const ROBOT: &str = "🤖";
@@ -25,7 +30,7 @@ fn show_agent_id() -> bool {
 
 fn agent_label(agent_id: &str) -> Option<String> {
     if show_agent_id() {
-        Some(format!("[{}]", agent_id))
+        Some(format!("[{}]", sanitize_ansi(agent_id)))
     } else {
         None
     }
@@ -44,8 +49,70 @@ fn is_duplicate(key: &str) -> bool {
     !set.insert(key.to_string())
 }
 
+/// Strip everything from untrusted text except tabs, newlines, printable
+/// characters, and well-formed CSI/SGR (color/style) escape sequences.
+/// Cursor-movement, OSC, and other control sequences are dropped outright,
+/// so a stray or malicious escape sequence in generated code or a build
+/// error can't corrupt the terminal.
+fn sanitize_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\t' | '\n' => out.push(c),
+            '\u{1b}' if chars.peek() == Some(&'[') => {
+                chars.next();
+                let mut params = String::new();
+                let mut final_byte = None;
+                for next in chars.by_ref() {
+                    if ('\u{20}'..='\u{3f}').contains(&next) {
+                        params.push(next);
+                    } else {
+                        final_byte = Some(next);
+                        break;
+                    }
+                }
+                // Only a well-formed SGR sequence (color/style) survives;
+                // cursor movement, screen clears, and malformed/truncated
+                // sequences are dropped entirely.
+                let is_sgr = params.chars().all(|p| p.is_ascii_digit() || p == ';');
+                if final_byte == Some('m') && is_sgr {
+                    out.push('\u{1b}');
+                    out.push('[');
+                    out.push_str(&params);
+                    out.push('m');
+                }
+            }
+            '\u{1b}' if chars.peek() == Some(&']') => {
+                chars.next();
+                // OSC sequence - consume through its BEL or ST terminator and drop it
+                for next in chars.by_ref() {
+                    if next == '\u{7}' {
+                        break;
+                    }
+                    if next == '\u{1b}' {
+                        if chars.peek() == Some(&'\\') {
+                            chars.next();
+                        }
+                        break;
+                    }
+                }
+            }
+            '\u{1b}' => {
+                // Unrecognized escape introducer - drop just it, not what follows
+            }
+            _ if c.is_control() => {} // drop other control chars (\r, bell, backspace, etc.)
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
 /// Print an agent status message (cyan)
 pub fn agent_status(agent_id: &str, message: &str) {
+    let message = sanitize_ansi(message);
     println!();
     if let Some(label) = agent_label(agent_id) {
         println!("{} {} {}", ROBOT, label.cyan().bold(), message.cyan());
@@ -56,6 +123,7 @@ pub fn agent_status(agent_id: &str, message: &str) {
 
 /// Print an agent info message (white/default)
 pub fn agent_info(agent_id: &str, message: &str) {
+    let message = sanitize_ansi(message);
     println!();
     if let Some(label) = agent_label(agent_id) {
         println!("{} {} {}", ROBOT, label.bright_white().bold(), message);
@@ -66,6 +134,7 @@ pub fn agent_info(agent_id: &str, message: &str) {
 
 /// Print an agent success message (green)
 pub fn agent_success(agent_id: &str, message: &str) {
+    let message = sanitize_ansi(message);
     println!();
     if let Some(label) = agent_label(agent_id) {
         println!("{} {} {}", ROBOT, label.green().bold(), message.green());
@@ -76,6 +145,7 @@ pub fn agent_success(agent_id: &str, message: &str) {
 
 /// Print an agent warning message (yellow)
 pub fn agent_warn(agent_id: &str, message: &str) {
+    let message = sanitize_ansi(message);
     println!();
     if let Some(label) = agent_label(agent_id) {
         println!("{} {} {}", ROBOT, label.yellow().bold(), message.yellow());
@@ -86,6 +156,7 @@ pub fn agent_warn(agent_id: &str, message: &str) {
 
 /// Print an agent error message (red)
 pub fn agent_error(agent_id: &str, message: &str) {
+    let message = sanitize_ansi(message);
     println!();
     if let Some(label) = agent_label(agent_id) {
         println!("{} {} {}", ROBOT, label.red().bold(), message.red());
@@ -100,6 +171,7 @@ pub fn peer_send_text(agent_id: &str, content: &str) {
     if is_duplicate(&key) {
         return;
     }
+    let content = sanitize_ansi(content);
     println!();
     if let Some(label) = agent_label(agent_id) {
         println!(
@@ -120,6 +192,7 @@ pub fn peer_recv_text(agent_id: &str, content: &str) {
     if is_duplicate(&key) {
         return;
     }
+    let content = sanitize_ansi(content);
     println!();
     if let Some(label) = agent_label(agent_id) {
         println!(
@@ -186,6 +259,7 @@ pub fn peer_recv_number(agent_id: &str, value: u64) {
 
 /// Print a peer connection event (bright magenta)
 pub fn peer_event(agent_id: &str, message: &str) {
+    let message = sanitize_ansi(message);
     println!();
     if let Some(label) = agent_label(agent_id) {
         println!(
@@ -268,8 +342,50 @@ pub fn config_item(agent_id: &str, key: &str, value: &str) {
     }
 }
 
-/// Print a code block
+static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+static THEME_SET: OnceLock<ThemeSet> = OnceLock::new();
+
+/// Whether highlighted/colored output should be emitted at all, honoring
+/// `NO_COLOR` and falling back to plain text when stdout isn't a tty.
+fn color_enabled() -> bool {
+    env::var("NO_COLOR").is_err() && std::io::stdout().is_terminal()
+}
+
+/// Syntax-highlight `code` as `language` (a syntect syntax name, e.g.
+/// "Rust") using a bundled theme, returning one 24-bit-SGR-colored,
+/// reset-terminated string per line. Falls back to `None` (letting the
+/// caller use its plain coloring) when color is disabled or the language
+/// or theme isn't recognized.
+fn highlight_lines(code: &str, language: &str) -> Option<Vec<String>> {
+    if !color_enabled() {
+        return None;
+    }
+
+    let syntax_set = SYNTAX_SET.get_or_init(SyntaxSet::load_defaults_newlines);
+    let theme_set = THEME_SET.get_or_init(ThemeSet::load_defaults);
+    let syntax = syntax_set.find_syntax_by_name(language)?;
+    let theme = theme_set.themes.get("base16-ocean.dark")?;
+    let mut highlighter = HighlightLines::new(syntax, theme);
+
+    let mut lines = Vec::new();
+    for line in code.lines() {
+        let ranges = highlighter.highlight_line(line, syntax_set).ok()?;
+        lines.push(format!("{}\x1b[0m", as_24_bit_terminal_escaped(&ranges, false)));
+    }
+    Some(lines)
+}
+
+/// Print a code block, assuming Rust source.
 pub fn code_block(agent_id: &str, code: &str) {
+    code_block_with_language(agent_id, code, "Rust");
+}
+
+/// Print a code block, syntax-highlighted via a bundled syntect syntax for
+/// `language` when color is available. Falls back to the flat
+/// `bright_yellow` coloring when highlighting fails, the language isn't
+/// recognized, or `NO_COLOR`/non-tty is detected.
+pub fn code_block_with_language(agent_id: &str, code: &str, language: &str) {
+    let code = sanitize_ansi(code);
     println!();
     if let Some(label) = agent_label(agent_id) {
         println!("{} {} Generated code:", ROBOT, label.bright_white().bold());
@@ -277,8 +393,17 @@ pub fn code_block(agent_id: &str, code: &str) {
         println!("{} Generated code:", ROBOT);
     }
     println!("{}", "```".dimmed());
-    for line in code.lines() {
-        println!("  {}", line.bright_yellow());
+    match highlight_lines(&code, language) {
+        Some(lines) => {
+            for line in lines {
+                println!("  {}", line);
+            }
+        }
+        None => {
+            for line in code.lines() {
+                println!("  {}", line.bright_yellow());
+            }
+        }
     }
     println!("{}", "```".dimmed());
 }
@@ -0,0 +1,203 @@
+use chrono::Utc;
+use rusqlite::{params, Connection};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::sync::Mutex;
+use tokio::task;
+
+#[derive(Error, Debug)]
+pub enum StorageError {
+    #[error("Database error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("Database task panicked: {0}")]
+    Join(#[from] task::JoinError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageDirection {
+    Sent,
+    Received,
+}
+
+impl MessageDirection {
+    fn as_str(self) -> &'static str {
+        match self {
+            MessageDirection::Sent => "sent",
+            MessageDirection::Received => "received",
+        }
+    }
+}
+
+/// One row of persisted conversation history.
+#[derive(Debug, Clone)]
+pub struct StoredMessage {
+    pub peer_id: String,
+    pub direction: MessageDirection,
+    pub content: String,
+    /// RFC 3339 UTC timestamp, as stored
+    pub timestamp: String,
+}
+
+/// In-memory conversation state rehydrated from the database on startup.
+#[derive(Debug, Default)]
+pub struct ConversationCounters {
+    pub sent: HashMap<String, usize>,
+    pub received: HashMap<String, usize>,
+    pub completed: HashSet<String>,
+}
+
+/// SQLite-backed record of every sent/received message and completed
+/// conversation, so a restarted agent doesn't forget state and re-greet
+/// peers it already finished talking to. Queries run on the blocking pool
+/// since `rusqlite` is synchronous; the connection is guarded by a
+/// `tokio::sync::Mutex` so only one blocking task touches it at a time.
+#[derive(Clone)]
+pub struct Storage {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl Storage {
+    pub async fn open(path: String) -> Result<Self, StorageError> {
+        let conn = task::spawn_blocking(move || -> Result<Connection, StorageError> {
+            let conn = Connection::open(path)?;
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS messages (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    peer_id TEXT NOT NULL,
+                    direction TEXT NOT NULL,
+                    content TEXT NOT NULL,
+                    timestamp TEXT NOT NULL
+                );
+                CREATE TABLE IF NOT EXISTS completed_conversations (
+                    peer_id TEXT PRIMARY KEY
+                );",
+            )?;
+            Ok(conn)
+        })
+        .await??;
+
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// Record one sent or received message with the current UTC timestamp.
+    pub async fn record_message(
+        &self,
+        peer_id: &str,
+        direction: MessageDirection,
+        content: &str,
+    ) -> Result<(), StorageError> {
+        let peer_id = peer_id.to_string();
+        let content = content.to_string();
+        let direction = direction.as_str();
+        let timestamp = Utc::now().to_rfc3339();
+        let conn = self.conn.clone();
+
+        task::spawn_blocking(move || {
+            let conn = conn.blocking_lock();
+            conn.execute(
+                "INSERT INTO messages (peer_id, direction, content, timestamp) VALUES (?1, ?2, ?3, ?4)",
+                params![peer_id, direction, content, timestamp],
+            )
+        })
+        .await??;
+
+        Ok(())
+    }
+
+    /// Mark a peer's conversation as complete (idempotent).
+    pub async fn mark_conversation_complete(&self, peer_id: &str) -> Result<(), StorageError> {
+        let peer_id = peer_id.to_string();
+        let conn = self.conn.clone();
+
+        task::spawn_blocking(move || {
+            let conn = conn.blocking_lock();
+            conn.execute(
+                "INSERT OR IGNORE INTO completed_conversations (peer_id) VALUES (?1)",
+                params![peer_id],
+            )
+        })
+        .await??;
+
+        Ok(())
+    }
+
+    /// Rehydrate in-memory conversation counters and the completed-set from
+    /// persisted history, so limits and logging survive a restart.
+    pub async fn load_counters(&self) -> Result<ConversationCounters, StorageError> {
+        let conn = self.conn.clone();
+
+        task::spawn_blocking(move || -> Result<ConversationCounters, rusqlite::Error> {
+            let conn = conn.blocking_lock();
+            let mut counters = ConversationCounters::default();
+
+            let mut stmt = conn.prepare(
+                "SELECT peer_id, direction, COUNT(*) FROM messages GROUP BY peer_id, direction",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                let peer_id: String = row.get(0)?;
+                let direction: String = row.get(1)?;
+                let count: i64 = row.get(2)?;
+                Ok((peer_id, direction, count as usize))
+            })?;
+            for row in rows {
+                let (peer_id, direction, count) = row?;
+                match direction.as_str() {
+                    "sent" => {
+                        counters.sent.insert(peer_id, count);
+                    }
+                    "received" => {
+                        counters.received.insert(peer_id, count);
+                    }
+                    _ => {}
+                }
+            }
+
+            let mut stmt = conn.prepare("SELECT peer_id FROM completed_conversations")?;
+            let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+            for row in rows {
+                counters.completed.insert(row?);
+            }
+
+            Ok(counters)
+        })
+        .await?
+        .map_err(StorageError::from)
+    }
+
+    /// A peer's full transcript, oldest message first.
+    pub async fn transcript(&self, peer_id: &str) -> Result<Vec<StoredMessage>, StorageError> {
+        let peer_id = peer_id.to_string();
+        let conn = self.conn.clone();
+
+        task::spawn_blocking(move || -> Result<Vec<StoredMessage>, rusqlite::Error> {
+            let conn = conn.blocking_lock();
+            let mut stmt = conn.prepare(
+                "SELECT peer_id, direction, content, timestamp FROM messages \
+                 WHERE peer_id = ?1 ORDER BY id ASC",
+            )?;
+            let rows = stmt.query_map(params![peer_id], |row| {
+                let peer_id: String = row.get(0)?;
+                let direction: String = row.get(1)?;
+                let content: String = row.get(2)?;
+                let timestamp: String = row.get(3)?;
+                let direction = if direction == "sent" {
+                    MessageDirection::Sent
+                } else {
+                    MessageDirection::Received
+                };
+                Ok(StoredMessage {
+                    peer_id,
+                    direction,
+                    content,
+                    timestamp,
+                })
+            })?;
+            rows.collect()
+        })
+        .await?
+        .map_err(StorageError::from)
+    }
+}
@@ -0,0 +1,142 @@
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher as NotifyWatcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::RecvTimeoutError;
+use std::thread;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::sync::mpsc;
+
+#[derive(Error, Debug)]
+pub enum WatcherError {
+    #[error("Failed to watch workspace: {0}")]
+    Notify(#[from] notify::Error),
+}
+
+/// Kind of filesystem change observed for a workspace path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Created,
+    Modified,
+    Removed,
+}
+
+/// A single coalesced change to a workspace file, tagged with the agent
+/// whose project subdirectory it falls under.
+#[derive(Debug, Clone)]
+pub struct WorkspaceChange {
+    pub agent_id: String,
+    pub path: PathBuf,
+    pub kind: ChangeKind,
+}
+
+/// How long to coalesce bursts of raw filesystem events for the same path
+/// before emitting one `WorkspaceChange`, mirroring `log_mux`'s card flush
+/// window.
+const DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Watches the workspace directory for external edits to agent-owned files
+/// (e.g. a peer or the user editing `src/**` or `Cargo.toml` out-of-band),
+/// so an agent can notice and re-read a file before a build instead of
+/// clobbering it with a stale full-file `FileWriter::write_file`.
+pub struct Watcher {
+    _inner: RecommendedWatcher,
+}
+
+impl Watcher {
+    /// Start watching `workspace` and return the watcher - keep it alive
+    /// for as long as events should keep flowing, since dropping it stops
+    /// the underlying OS watch - along with a receiver of debounced,
+    /// path-filtered `WorkspaceChange`s.
+    pub fn watch(
+        workspace: impl Into<PathBuf>,
+    ) -> Result<(Self, mpsc::UnboundedReceiver<WorkspaceChange>), WatcherError> {
+        let workspace = workspace.into();
+        let (raw_tx, raw_rx) = std::sync::mpsc::channel();
+        let mut inner = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            if let Ok(event) = event {
+                let _ = raw_tx.send(event);
+            }
+        })?;
+        inner.watch(&workspace, RecursiveMode::Recursive)?;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        thread::spawn(move || debounce_loop(raw_rx, workspace, tx));
+
+        Ok((Self { _inner: inner }, rx))
+    }
+}
+
+fn debounce_loop(
+    raw_rx: std::sync::mpsc::Receiver<Event>,
+    workspace: PathBuf,
+    tx: mpsc::UnboundedSender<WorkspaceChange>,
+) {
+    let mut pending: HashMap<PathBuf, (ChangeKind, Instant)> = HashMap::new();
+
+    loop {
+        match raw_rx.recv_timeout(DEBOUNCE) {
+            Ok(event) => {
+                if let Some(kind) = classify(&event.kind) {
+                    for path in event.paths {
+                        if is_relevant(&workspace, &path) {
+                            pending.insert(path, (kind, Instant::now()));
+                        }
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        let now = Instant::now();
+        let ready: Vec<PathBuf> = pending
+            .iter()
+            .filter(|(_, (_, seen))| now.duration_since(*seen) >= DEBOUNCE)
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        for path in ready {
+            if let Some((kind, _)) = pending.remove(&path) {
+                let Some(agent_id) = owning_agent(&workspace, &path) else {
+                    continue;
+                };
+                let change = WorkspaceChange { agent_id, path, kind };
+                if tx.send(change).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+fn classify(kind: &EventKind) -> Option<ChangeKind> {
+    match kind {
+        EventKind::Create(_) => Some(ChangeKind::Created),
+        EventKind::Modify(_) => Some(ChangeKind::Modified),
+        EventKind::Remove(_) => Some(ChangeKind::Removed),
+        _ => None,
+    }
+}
+
+/// Only `src/**` files and `Cargo.toml`, within an agent's project
+/// subdirectory, are worth surfacing to callers.
+fn is_relevant(workspace: &Path, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(workspace) else {
+        return false;
+    };
+    let mut components = relative.components();
+    components.next(); // the agent's project subdirectory
+    let remainder: PathBuf = components.collect();
+    remainder.starts_with("src") || remainder.file_name().is_some_and(|name| name == "Cargo.toml")
+}
+
+/// The agent a path belongs to is the first path component under the
+/// workspace root, i.e. its project subdirectory name.
+fn owning_agent(workspace: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(workspace).ok()?;
+    relative
+        .components()
+        .next()
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+}
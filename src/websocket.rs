@@ -8,55 +8,778 @@ use axum::{
     routing::get,
     Router,
 };
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use futures_util::{
-    stream::{SplitSink, SplitStream},
+    stream::{FuturesUnordered, SplitSink, SplitStream},
     SinkExt, StreamExt,
 };
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::{broadcast, mpsc, RwLock};
+use std::time::Instant;
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::{mpsc, oneshot, Mutex, RwLock};
 use tokio::time::{timeout, Duration};
-use tokio_tungstenite::{connect_async, tungstenite::Message as TungsteniteMessage};
+use tokio_tungstenite::{
+    accept_async, client_async, connect_async, tungstenite::Message as TungsteniteMessage,
+    WebSocketStream,
+};
+use uuid::Uuid;
+
+/// Hop limit new gossip messages start with; bounds how far a flood can travel.
+const DEFAULT_GOSSIP_TTL: u8 = 8;
+
+/// How many recent gossip message IDs we remember before evicting the oldest.
+const GOSSIP_SEEN_CAPACITY: usize = 1024;
+
+/// Default time to wait for a correlated `Response` before giving up.
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Max number of peer dial attempts in flight at once during a bulk connect.
+const MAX_CONCURRENT_DIALS: usize = 8;
+
+/// Delay between spawning successive dial attempts, so a long peer list
+/// doesn't flood the network with connection attempts all at once on boot.
+const DIAL_STAGGER: Duration = Duration::from_millis(50);
+
+/// Overall timeout for one peer's full handshake (TCP connect + presence
+/// exchange) when dialing concurrently, so a single slow peer can't stall
+/// the rest of the batch.
+const DIAL_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a peer can go without any inbound message before it's considered dead.
+pub const DEFAULT_LIVENESS_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default capacity of the bounded channel carrying parsed messages from the
+/// transport layer to the agent's conversation handler.
+pub const DEFAULT_INCOMING_CHANNEL_CAPACITY: usize = 64;
+
+/// Errors returned by [`WebSocketServer::request`].
+#[derive(Error, Debug)]
+pub enum RequestError {
+    #[error("request timed out waiting for a response")]
+    Timeout,
+    #[error("peer responded with an error: {0}")]
+    Remote(String),
+}
 
 /// Protocol message types for agent-to-agent communication
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum AgentMessage {
-    /// Announce presence when connecting
+    /// Sent by the accepting side of a fresh connection, carrying a random
+    /// nonce the dialer must sign with its private key before its `Presence`
+    /// is trusted; see [`AgentMessage::Presence`].
+    #[serde(rename = "challenge")]
+    Challenge { nonce: String },
+
+    /// Announce presence when connecting. `signature` is an ed25519
+    /// signature, produced with the private key behind `public_key`, over
+    /// the nonce from the `Challenge` the receiver issued for this
+    /// connection - proof that `agent_id` isn't just a self-asserted string.
+    /// `ack_nonce` is a nonce of the sender's own choosing that it expects
+    /// the receiver to sign and echo back in `PresenceAck`, so trust runs
+    /// both ways: the receiver can't vouch for its own identity without
+    /// proving it holds the private key behind the `public_key` it counters
+    /// with.
     #[serde(rename = "presence")]
-    Presence { agent_id: String },
+    Presence {
+        agent_id: String,
+        public_key: String,
+        signature: String,
+        ack_nonce: String,
+    },
 
-    /// Acknowledge a presence message
+    /// Acknowledge a presence message. `signature` is an ed25519 signature
+    /// over the peer's `ack_nonce`, proving `agent_id` isn't just a
+    /// self-asserted string here either - see [`AgentMessage::Presence`].
     #[serde(rename = "presence_ack")]
-    PresenceAck { agent_id: String },
+    PresenceAck {
+        agent_id: String,
+        public_key: String,
+        signature: String,
+    },
 
-    /// Ping to check if peer is alive
+    /// Ping to check if peer is alive. `peer_list_hash` piggybacks a stable
+    /// hash of the sender's known-peer list (see [`peer_list_hash`]) so the
+    /// receiver can tell, without waiting for the next periodic gossip
+    /// interval, whether it already has everything the sender knows about.
     #[serde(rename = "ping")]
-    Ping { agent_id: String, seq: u64 },
+    Ping {
+        agent_id: String,
+        seq: u64,
+        peer_list_hash: u64,
+    },
 
-    /// Pong response to ping
+    /// Pong response to ping, carrying the same kind of hash as `Ping`.
     #[serde(rename = "pong")]
-    Pong { agent_id: String, seq: u64 },
+    Pong {
+        agent_id: String,
+        seq: u64,
+        peer_list_hash: u64,
+    },
 
     /// Text message (for LLM-generated content)
     #[serde(rename = "text")]
-    Text { agent_id: String, content: String },
+    Text {
+        /// The agent that authored this message, preserved unchanged as it's
+        /// relayed across hops (as opposed to whichever peer forwarded it to us)
+        origin: String,
+        content: String,
+        /// Stable ID used for gossip dedup (origin agent + sequence, or a UUID)
+        msg_id: String,
+        /// Remaining hops before this message stops propagating
+        ttl: u8,
+        /// When set, this message is addressed to one agent (possibly not a
+        /// direct peer) and is unicast hop-by-hop via the routing table
+        /// instead of flooded to every connection; `None` keeps the
+        /// original broadcast-to-everyone behavior.
+        dest: Option<String>,
+    },
 
     /// Number message (for simple testing without LLM)
     #[serde(rename = "number")]
-    Number { agent_id: String, value: u64 },
+    Number {
+        /// The agent that authored this message, preserved unchanged as it's
+        /// relayed across hops (as opposed to whichever peer forwarded it to us)
+        origin: String,
+        value: u64,
+        msg_id: String,
+        ttl: u8,
+        /// See `Text::dest`.
+        dest: Option<String>,
+    },
+
+    /// A request addressed to a specific peer, awaiting a correlated `Response`
+    #[serde(rename = "request")]
+    Request {
+        request_id: u64,
+        agent_id: String,
+        target_agent: String,
+        payload: String,
+    },
+
+    /// A reply to a `Request`, correlated by `request_id`. `error` is set
+    /// instead of `payload` when the responder failed to handle the request,
+    /// so the failure can surface as an `Err` on the requester side rather
+    /// than being disguised as a successful empty payload. Routed the same
+    /// way as `Text`/`Number` via `dest`/`ttl`, since the requester may be
+    /// more than one hop away.
+    #[serde(rename = "response")]
+    Response {
+        request_id: u64,
+        agent_id: String,
+        payload: Option<String>,
+        error: Option<String>,
+        dest: Option<String>,
+        ttl: u8,
+    },
+
+    /// Delegate a code-generation task to `target_agent`, correlated by `request_id`
+    #[serde(rename = "code_request")]
+    CodeRequest {
+        request_id: String,
+        agent_id: String,
+        target_agent: String,
+        prompt: String,
+        project_dir: String,
+    },
+
+    /// The result of a delegated `CodeRequest`, correlated by `request_id`
+    #[serde(rename = "code_response")]
+    CodeResponse {
+        request_id: String,
+        agent_id: String,
+        code: String,
+        run_output: String,
+        success: bool,
+    },
+
+    /// Periodically exchanged with each connected peer (and right after a
+    /// new connection is established) so a node can bootstrap from a single
+    /// seed address and have the retry loop grow the mesh to full
+    /// connectivity on its own, one hop at a time
+    #[serde(rename = "peer_list")]
+    PeerList {
+        agent_id: String,
+        known_peers: Vec<PeerInfo>,
+    },
+}
+
+/// One entry in a [`AgentMessage::PeerList`]: a peer the sender is currently
+/// connected to, and the address it dialed to reach it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerInfo {
+    pub agent_id: String,
+    pub address: String,
+}
+
+/// A stable hash of a known-peer list, independent of iteration order, for
+/// piggybacking on `Ping`/`Pong` so a node can tell a peer's view of the
+/// mesh has changed without waiting for the next periodic `PeerList` gossip.
+pub fn peer_list_hash(peers: &[PeerInfo]) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut sorted: Vec<&PeerInfo> = peers.iter().collect();
+    sorted.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+
+    let mut hasher = DefaultHasher::new();
+    for peer in sorted {
+        peer.agent_id.hash(&mut hasher);
+        peer.address.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Hex-encode an ed25519 signature over `message`, proving possession of
+/// `signing_key` - used to answer a handshake `Challenge`.
+fn sign_hex(signing_key: &SigningKey, message: &[u8]) -> String {
+    hex::encode(signing_key.sign(message).to_bytes())
+}
+
+/// Verify a hex-encoded ed25519 `signature` over `message` against a
+/// hex-encoded public key, returning `false` (rather than erroring) on any
+/// malformed input so callers can treat decode failures the same as a bad
+/// signature.
+fn verify_presence_signature(public_key_hex: &str, message: &[u8], signature_hex: &str) -> bool {
+    let Ok(key_bytes) = hex::decode(public_key_hex) else {
+        return false;
+    };
+    let Ok(key_bytes) = <[u8; 32]>::try_from(key_bytes.as_slice()) else {
+        return false;
+    };
+    let Ok(verifying_key) = VerifyingKey::from_bytes(&key_bytes) else {
+        return false;
+    };
+    let Ok(sig_bytes) = hex::decode(signature_hex) else {
+        return false;
+    };
+    let Ok(sig_bytes) = <[u8; 64]>::try_from(sig_bytes.as_slice()) else {
+        return false;
+    };
+    verifying_key
+        .verify(message, &Signature::from_bytes(&sig_bytes))
+        .is_ok()
+}
+
+/// Trust-on-first-use check against [`KnownKeys`]: the first `Presence` seen
+/// for `peer_id` pins its public key, and every later one must match it.
+/// Returns `false` if a different key is now being asserted for a
+/// previously-pinned `peer_id`.
+async fn check_and_pin_key(known_keys: &KnownKeys, peer_id: &str, public_key: &str) -> bool {
+    let mut known_keys = known_keys.write().await;
+    match known_keys.get(peer_id) {
+        Some(pinned) => pinned == public_key,
+        None => {
+            known_keys.insert(peer_id.to_string(), public_key.to_string());
+            true
+        }
+    }
 }
 
 impl AgentMessage {
+    /// The logical sender of this message. For directly-exchanged variants
+    /// this is whoever sent it to us; for gossiped variants it's the original
+    /// author, which may be several hops away and is *not* necessarily the
+    /// peer we received this particular copy from — callers tracking a
+    /// connection's liveness should prefer the connection's own known
+    /// identity over this for `Text`/`Number`.
     pub fn sender_id(&self) -> &str {
         match self {
-            AgentMessage::Presence { agent_id } => agent_id,
-            AgentMessage::PresenceAck { agent_id } => agent_id,
+            // Not agent-authored, so there's no sender identity to report yet.
+            AgentMessage::Challenge { .. } => "",
+            AgentMessage::Presence { agent_id, .. } => agent_id,
+            AgentMessage::PresenceAck { agent_id, .. } => agent_id,
             AgentMessage::Ping { agent_id, .. } => agent_id,
             AgentMessage::Pong { agent_id, .. } => agent_id,
-            AgentMessage::Text { agent_id, .. } => agent_id,
-            AgentMessage::Number { agent_id, .. } => agent_id,
+            AgentMessage::Text { origin, .. } => origin,
+            AgentMessage::Number { origin, .. } => origin,
+            AgentMessage::Request { agent_id, .. } => agent_id,
+            AgentMessage::Response { agent_id, .. } => agent_id,
+            AgentMessage::CodeRequest { agent_id, .. } => agent_id,
+            AgentMessage::CodeResponse { agent_id, .. } => agent_id,
+            AgentMessage::PeerList { agent_id, .. } => agent_id,
+        }
+    }
+
+    /// Short name for this variant, matching its wire `type` tag. Used to
+    /// break traffic stats down by message kind.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            AgentMessage::Challenge { .. } => "challenge",
+            AgentMessage::Presence { .. } => "presence",
+            AgentMessage::PresenceAck { .. } => "presence_ack",
+            AgentMessage::Ping { .. } => "ping",
+            AgentMessage::Pong { .. } => "pong",
+            AgentMessage::Text { .. } => "text",
+            AgentMessage::Number { .. } => "number",
+            AgentMessage::Request { .. } => "request",
+            AgentMessage::Response { .. } => "response",
+            AgentMessage::CodeRequest { .. } => "code_request",
+            AgentMessage::CodeResponse { .. } => "code_response",
+            AgentMessage::PeerList { .. } => "peer_list",
+        }
+    }
+
+    /// Build a `Text` message carrying a fresh gossip ID and default TTL,
+    /// broadcast to every connected peer.
+    pub fn text(origin: String, content: String) -> Self {
+        AgentMessage::Text {
+            origin,
+            content,
+            msg_id: Uuid::new_v4().to_string(),
+            ttl: DEFAULT_GOSSIP_TTL,
+            dest: None,
+        }
+    }
+
+    /// Build a `Number` message carrying a fresh gossip ID and default TTL,
+    /// broadcast to every connected peer.
+    pub fn number(origin: String, value: u64) -> Self {
+        AgentMessage::Number {
+            origin,
+            value,
+            msg_id: Uuid::new_v4().to_string(),
+            ttl: DEFAULT_GOSSIP_TTL,
+            dest: None,
+        }
+    }
+
+    /// Build a `Text` message addressed to `target_agent`, to be routed
+    /// hop-by-hop instead of flooded to every connection.
+    pub fn text_to(origin: String, content: String, target_agent: String) -> Self {
+        AgentMessage::Text {
+            origin,
+            content,
+            msg_id: Uuid::new_v4().to_string(),
+            ttl: DEFAULT_GOSSIP_TTL,
+            dest: Some(target_agent),
+        }
+    }
+
+    /// The agent this message is addressed to, if it's a routable variant
+    /// carrying a `dest`. `None` both for variants with no such field and
+    /// for an unaddressed (broadcast) `dest: None`.
+    fn dest(&self) -> Option<&str> {
+        match self {
+            AgentMessage::Text { dest, .. } => dest.as_deref(),
+            AgentMessage::Number { dest, .. } => dest.as_deref(),
+            AgentMessage::Response { dest, .. } => dest.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The gossip dedup ID for messages that flood the mesh, if this variant has one.
+    fn gossip_id(&self) -> Option<&str> {
+        match self {
+            AgentMessage::Text { msg_id, .. } => Some(msg_id),
+            AgentMessage::Number { msg_id, .. } => Some(msg_id),
+            _ => None,
+        }
+    }
+
+    /// Remaining hop count for gossiped or routed variants, if this variant has one.
+    fn ttl(&self) -> Option<u8> {
+        match self {
+            AgentMessage::Text { ttl, .. } => Some(*ttl),
+            AgentMessage::Number { ttl, .. } => Some(*ttl),
+            AgentMessage::Response { ttl, .. } => Some(*ttl),
+            _ => None,
+        }
+    }
+
+    /// Returns a copy of this message with its TTL replaced, if it's a gossiped or routed variant.
+    fn with_ttl(mut self, new_ttl: u8) -> Self {
+        match &mut self {
+            AgentMessage::Text { ttl, .. } => *ttl = new_ttl,
+            AgentMessage::Number { ttl, .. } => *ttl = new_ttl,
+            AgentMessage::Response { ttl, .. } => *ttl = new_ttl,
+            _ => {}
+        }
+        self
+    }
+}
+
+/// Bounded record of recently-seen gossip message IDs, used to suppress
+/// re-delivery and forwarding loops without growing memory unboundedly.
+struct SeenSet {
+    order: VecDeque<String>,
+    ids: HashSet<String>,
+}
+
+impl SeenSet {
+    fn new() -> Self {
+        Self {
+            order: VecDeque::new(),
+            ids: HashSet::new(),
+        }
+    }
+
+    /// Records `msg_id`, returning `true` the first time it's seen.
+    fn insert(&mut self, msg_id: String) -> bool {
+        if !self.ids.insert(msg_id.clone()) {
+            return false;
+        }
+        self.order.push_back(msg_id);
+        if self.order.len() > GOSSIP_SEEN_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+/// Message/byte tally for one `AgentMessage` variant exchanged with a peer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MessageTypeStats {
+    pub messages: u64,
+    pub bytes: u64,
+}
+
+impl MessageTypeStats {
+    fn record(&mut self, bytes: usize) {
+        self.messages += 1;
+        self.bytes += bytes as u64;
+    }
+}
+
+/// How many recent ping RTT samples to keep per peer for [`PeerStats`]'s
+/// `avg_ping`/`med_ping`/`max_ping` accessors.
+const RTT_SAMPLE_CAPACITY: usize = 10;
+
+/// Per-peer traffic tallies, broken down by [`AgentMessage::variant_name`],
+/// plus connection lifecycle counters and recent ping RTT samples. See
+/// [`WebSocketServer::peer_stats`].
+#[derive(Debug, Clone, Default)]
+pub struct PeerStats {
+    pub sent: HashMap<&'static str, MessageTypeStats>,
+    pub received: HashMap<&'static str, MessageTypeStats>,
+    pub connects: u64,
+    pub disconnects: u64,
+    /// Most recent `RTT_SAMPLE_CAPACITY` measured ping round-trip times,
+    /// oldest first.
+    rtt_samples: VecDeque<Duration>,
+}
+
+impl PeerStats {
+    fn record_rtt(&mut self, rtt: Duration) {
+        self.rtt_samples.push_back(rtt);
+        if self.rtt_samples.len() > RTT_SAMPLE_CAPACITY {
+            self.rtt_samples.pop_front();
+        }
+    }
+
+    /// Average of the recorded RTT samples, or `None` if none have arrived yet.
+    pub fn avg_ping(&self) -> Option<Duration> {
+        if self.rtt_samples.is_empty() {
+            return None;
+        }
+        let total: Duration = self.rtt_samples.iter().sum();
+        Some(total / self.rtt_samples.len() as u32)
+    }
+
+    /// Median of the recorded RTT samples, or `None` if none have arrived yet.
+    pub fn med_ping(&self) -> Option<Duration> {
+        if self.rtt_samples.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<Duration> = self.rtt_samples.iter().copied().collect();
+        sorted.sort();
+        Some(sorted[sorted.len() / 2])
+    }
+
+    /// Largest of the recorded RTT samples, or `None` if none have arrived yet.
+    pub fn max_ping(&self) -> Option<Duration> {
+        self.rtt_samples.iter().max().copied()
+    }
+}
+
+/// Shared traffic-stats registry, keyed by peer agent ID.
+type TrafficStats = Arc<RwLock<HashMap<String, PeerStats>>>;
+
+/// Oneshots awaiting a correlated `Response`, keyed by `request_id`. Resolves
+/// to `Err` when the responder replies with a failure instead of a payload.
+type PendingRequests = Arc<Mutex<HashMap<u64, oneshot::Sender<Result<String, String>>>>>;
+
+/// Routing table for addressed (`dest: Some(..)`) messages: maps a
+/// non-adjacent agent's ID to the directly-connected peer that should next
+/// receive the message on its way there. Populated from gossiped `PeerList`s.
+type Routes = Arc<RwLock<HashMap<String, String>>>;
+
+/// Live outgoing connection ID for each directly-connected peer, so an
+/// addressed message can be unicast to one specific peer instead of
+/// broadcast to every connection.
+type PeerConnections = Arc<RwLock<HashMap<String, u64>>>;
+
+/// Trust-on-first-use pin of each `agent_id`'s ed25519 public key (hex),
+/// recorded the first time its `Presence` signature verifies. A later
+/// handshake claiming the same `agent_id` with a different key is rejected,
+/// so possessing *some* keypair is no longer enough to assert an identity
+/// someone else has already established with this server.
+type KnownKeys = Arc<RwLock<HashMap<String, String>>>;
+
+async fn record_sent(stats: &TrafficStats, peer_id: &str, message: &AgentMessage, bytes: usize) {
+    stats
+        .write()
+        .await
+        .entry(peer_id.to_string())
+        .or_default()
+        .sent
+        .entry(message.variant_name())
+        .or_default()
+        .record(bytes);
+}
+
+async fn record_received(stats: &TrafficStats, peer_id: &str, message: &AgentMessage, bytes: usize) {
+    stats
+        .write()
+        .await
+        .entry(peer_id.to_string())
+        .or_default()
+        .received
+        .entry(message.variant_name())
+        .or_default()
+        .record(bytes);
+}
+
+async fn record_connect(stats: &TrafficStats, peer_id: &str) {
+    stats.write().await.entry(peer_id.to_string()).or_default().connects += 1;
+}
+
+async fn record_disconnect(stats: &TrafficStats, peer_id: &str) {
+    stats
+        .write()
+        .await
+        .entry(peer_id.to_string())
+        .or_default()
+        .disconnects += 1;
+}
+
+async fn record_rtt(stats: &TrafficStats, peer_id: &str, rtt: Duration) {
+    stats
+        .write()
+        .await
+        .entry(peer_id.to_string())
+        .or_default()
+        .record_rtt(rtt);
+}
+
+/// Generate a fresh ed25519 keypair for a `WebSocketServer` instance,
+/// reusing the existing `rand` dependency rather than pulling in a second
+/// RNG just for key generation.
+fn generate_signing_key() -> SigningKey {
+    let mut seed = [0u8; 32];
+    rand::rng().fill_bytes(&mut seed);
+    SigningKey::from_bytes(&seed)
+}
+
+/// Fan-out registry of per-peer outgoing queues. Unbounded, because a slow
+/// peer's socket write should never stall delivery to the others — queuing
+/// one more serialized frame is far cheaper than blocking the sender. This
+/// is the counterpart to the bounded `incoming_tx`: a noisy peer can only
+/// ever push back on our ability to process *their* messages, never on our
+/// ability to send to everyone else.
+#[derive(Clone)]
+struct OutgoingHub {
+    senders: Arc<RwLock<HashMap<u64, mpsc::UnboundedSender<String>>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl OutgoingHub {
+    fn new() -> Self {
+        Self {
+            senders: Arc::new(RwLock::new(HashMap::new())),
+            next_id: Arc::new(AtomicU64::new(1)),
+        }
+    }
+
+    /// Register a connection's outgoing queue, returning a handle to
+    /// unregister it on disconnect and the receiver its writer task drains.
+    async fn register(&self) -> (u64, mpsc::UnboundedReceiver<String>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        self.senders.write().await.insert(id, tx);
+        (id, rx)
+    }
+
+    async fn unregister(&self, id: u64) {
+        self.senders.write().await.remove(&id);
+    }
+
+    /// Send `msg` to every registered peer connection.
+    async fn send(&self, msg: String) {
+        for sender in self.senders.read().await.values() {
+            let _ = sender.send(msg.clone());
+        }
+    }
+
+    /// Send `msg` to every registered peer connection except `exclude_id`,
+    /// so a re-flooded gossip message doesn't bounce straight back to
+    /// whichever peer just sent it to us.
+    async fn send_except(&self, msg: String, exclude_id: u64) {
+        for (id, sender) in self.senders.read().await.iter() {
+            if *id == exclude_id {
+                continue;
+            }
+            let _ = sender.send(msg.clone());
+        }
+    }
+
+    /// Send `msg` to exactly the connection registered as `id`, for
+    /// unicasting an addressed message toward a specific next hop. Returns
+    /// `false` if that connection is no longer registered.
+    async fn send_to(&self, id: u64, msg: String) -> bool {
+        match self.senders.read().await.get(&id) {
+            Some(sender) => sender.send(msg).is_ok(),
+            None => false,
+        }
+    }
+}
+
+/// Delivers `parsed` locally and, for gossiped variants, re-broadcasts it with
+/// a decremented TTL unless we've already seen its `msg_id` or its TTL has
+/// expired. Non-gossiped variants (e.g. `Ping`) are just forwarded once.
+/// `from_id` identifies the connection `parsed` arrived on, if any, so a
+/// gossip re-flood can skip sending it straight back where it came from.
+/// Addressed variants (`dest: Some(..)` not equal to `our_agent_id`) are
+/// unicast toward their next hop via `routes`/`peer_connections` instead of
+/// being flooded or delivered locally.
+#[allow(clippy::too_many_arguments)]
+async fn process_incoming_message(
+    parsed: AgentMessage,
+    outgoing: &OutgoingHub,
+    incoming_tx: &mpsc::Sender<AgentMessage>,
+    seen: &Arc<RwLock<SeenSet>>,
+    pending_requests: &PendingRequests,
+    our_agent_id: &str,
+    routes: &Routes,
+    peer_connections: &PeerConnections,
+    from_id: u64,
+) {
+    if let Some(dest) = parsed.dest() {
+        if dest != our_agent_id {
+            let dest = dest.to_string();
+            route_onward(parsed, &dest, outgoing, seen, routes, peer_connections).await;
+            return;
+        }
+    }
+
+    // Responses are consumed here to complete the matching `request()` call,
+    // not forwarded to the agent's generic incoming channel.
+    if let AgentMessage::Response {
+        request_id,
+        payload,
+        error,
+        ..
+    } = &parsed
+    {
+        if let Some(sender) = pending_requests.lock().await.remove(request_id) {
+            let result = match error {
+                Some(message) => Err(message.clone()),
+                None => Ok(payload.clone().unwrap_or_default()),
+            };
+            let _ = sender.send(result);
+        }
+        return;
+    }
+
+    if let Some(msg_id) = parsed.gossip_id() {
+        let is_new = seen.write().await.insert(msg_id.to_string());
+        if !is_new {
+            return;
+        }
+    }
+
+    log_peer_message_received(&parsed);
+    let ttl = parsed.ttl();
+    let _ = incoming_tx.send(parsed.clone()).await;
+
+    // Addressed messages are unicast-routed above instead of flooded, so
+    // only an unaddressed (`dest: None`) broadcast re-floods here.
+    if parsed.dest().is_none() {
+        if let Some(ttl) = ttl {
+            if ttl > 0 {
+                let forwarded = parsed.with_ttl(ttl - 1);
+                let msg = serde_json::to_string(&forwarded).unwrap();
+                outgoing.send_except(msg, from_id).await;
+            }
+        }
+    }
+}
+
+/// Forward an addressed message (`dest` not us) one hop closer to its
+/// target, decrementing its TTL, deduping on `msg_id` the same way a
+/// flooded gossip message would to guard against routing-table cycles, and
+/// dropping it with a log line if the TTL has expired or no route is known.
+async fn route_onward(
+    parsed: AgentMessage,
+    dest: &str,
+    outgoing: &OutgoingHub,
+    seen: &Arc<RwLock<SeenSet>>,
+    routes: &Routes,
+    peer_connections: &PeerConnections,
+) {
+    if let Some(msg_id) = parsed.gossip_id() {
+        let is_new = seen.write().await.insert(msg_id.to_string());
+        if !is_new {
+            return;
+        }
+    }
+
+    let ttl = parsed.ttl().unwrap_or(0);
+    if ttl == 0 {
+        output::agent_warn("router", &format!("Dropping message to {}: TTL expired", dest));
+        return;
+    }
+
+    let Some(next_hop) = routes.read().await.get(dest).cloned() else {
+        output::agent_warn("router", &format!("No route to {}, dropping message", dest));
+        return;
+    };
+
+    let Some(conn_id) = peer_connections.read().await.get(&next_hop).cloned() else {
+        output::agent_warn(
+            "router",
+            &format!("Route to {} via {} has no live connection, dropping message", dest, next_hop),
+        );
+        return;
+    };
+
+    let forwarded = parsed.with_ttl(ttl - 1);
+    let msg = serde_json::to_string(&forwarded).unwrap();
+    outgoing.send_to(conn_id, msg).await;
+}
+
+/// Which side initiated a session: we dialed out, or a peer dialed us.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionDirection {
+    Inbound,
+    Outbound,
+}
+
+/// A peer's dial target, either a TCP WebSocket URL or a filesystem path to
+/// a Unix domain socket. Local peers on the same host can use the latter to
+/// skip the loopback TCP stack entirely.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PeerAddress {
+    Tcp(String),
+    Unix(String),
+}
+
+impl PeerAddress {
+    /// Parse a peer address string. `unix:///path/to.sock` is treated as a
+    /// Unix socket path; anything else (e.g. `ws://host:port/ws`) is treated
+    /// as a TCP WebSocket URL.
+    pub fn parse(addr: &str) -> Self {
+        match addr.strip_prefix("unix://") {
+            Some(path) => PeerAddress::Unix(path.to_string()),
+            None => PeerAddress::Tcp(addr.to_string()),
         }
     }
 }
@@ -64,7 +787,7 @@ impl AgentMessage {
 /// Result of attempting to connect to a peer
 #[derive(Debug)]
 pub enum PeerConnectionResult {
-    Connected(String),
+    Connected(String, ConnectionDirection),
     Failed(String, String),
 }
 
@@ -72,45 +795,169 @@ pub enum PeerConnectionResult {
 pub struct WebSocketServer {
     agent_id: String,
     port: u16,
-    tx: broadcast::Sender<String>,
+    outgoing: OutgoingHub,
     connected_peers: Arc<RwLock<HashSet<String>>>,
     connected_urls: Arc<RwLock<HashSet<String>>>,
+    /// Reverse mapping from a connected peer's agent ID back to the URL we
+    /// dialed to reach it, so forgetting a peer (on liveness timeout or a
+    /// missed-ping teardown) also frees its URL up for redialing. Only
+    /// populated for outbound connections; inbound peers have no URL of ours.
+    peer_urls: Arc<RwLock<HashMap<String, String>>>,
     incoming_rx: Arc<RwLock<Option<mpsc::Receiver<AgentMessage>>>>,
     incoming_tx: mpsc::Sender<AgentMessage>,
+    gossip_seen: Arc<RwLock<SeenSet>>,
+    next_request_id: Arc<AtomicU64>,
+    pending_requests: PendingRequests,
+    last_seen: Arc<RwLock<HashMap<String, Instant>>>,
+    /// Subset of `connected_peers` that dialed us, rather than the other way
+    /// around; used to enforce `max_inbound` and to pick a non-reserved
+    /// inbound peer to evict when a reserved peer needs a slot.
+    inbound_peers: Arc<RwLock<HashSet<String>>>,
+    max_inbound: usize,
+    max_outbound: usize,
+    reserved_only: bool,
+    reserved_peers: Arc<HashSet<String>>,
+    /// Per-peer traffic tallies; see [`WebSocketServer::peer_stats`].
+    stats: TrafficStats,
+    /// See [`Routes`].
+    routes: Routes,
+    /// See [`PeerConnections`].
+    peer_connections: PeerConnections,
+    /// Proves this server's identity during the presence handshake; see
+    /// [`AgentMessage::Presence`].
+    signing_key: SigningKey,
+    /// See [`KnownKeys`].
+    known_keys: KnownKeys,
 }
 
 impl WebSocketServer {
     pub fn new(agent_id: String, port: u16) -> Self {
-        let (tx, _) = broadcast::channel(100);
-        let (incoming_tx, incoming_rx) = mpsc::channel(100);
+        Self::with_incoming_capacity(agent_id, port, DEFAULT_INCOMING_CHANNEL_CAPACITY)
+    }
+
+    /// Like [`WebSocketServer::new`], but with an explicit capacity for the
+    /// bounded incoming channel — the knob operators tune via `Config` to
+    /// control how much slack a noisy peer gets before its socket stalls.
+    /// Connection slots are unlimited and no reserved-peer restriction is
+    /// applied; use [`WebSocketServer::with_connection_limits`] for that.
+    pub fn with_incoming_capacity(agent_id: String, port: u16, incoming_capacity: usize) -> Self {
+        Self::with_connection_limits(
+            agent_id,
+            port,
+            incoming_capacity,
+            usize::MAX,
+            usize::MAX,
+            false,
+            Vec::new(),
+        )
+    }
+
+    /// Like [`WebSocketServer::with_incoming_capacity`], but with explicit
+    /// inbound/outbound connection slot caps and an optional reserved-peers
+    /// allowlist. When `reserved_only` is set, inbound connections from an
+    /// agent ID not in `reserved_peers` are rejected outright; reserved
+    /// peers may also evict a non-reserved inbound peer when slots are full.
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_connection_limits(
+        agent_id: String,
+        port: u16,
+        incoming_capacity: usize,
+        max_inbound: usize,
+        max_outbound: usize,
+        reserved_only: bool,
+        reserved_peers: Vec<String>,
+    ) -> Self {
+        let (incoming_tx, incoming_rx) = mpsc::channel(incoming_capacity);
         Self {
             agent_id,
             port,
-            tx,
+            outgoing: OutgoingHub::new(),
             connected_peers: Arc::new(RwLock::new(HashSet::new())),
             connected_urls: Arc::new(RwLock::new(HashSet::new())),
+            peer_urls: Arc::new(RwLock::new(HashMap::new())),
             incoming_rx: Arc::new(RwLock::new(Some(incoming_rx))),
             incoming_tx,
+            gossip_seen: Arc::new(RwLock::new(SeenSet::new())),
+            next_request_id: Arc::new(AtomicU64::new(1)),
+            pending_requests: Arc::new(Mutex::new(HashMap::new())),
+            last_seen: Arc::new(RwLock::new(HashMap::new())),
+            inbound_peers: Arc::new(RwLock::new(HashSet::new())),
+            max_inbound,
+            max_outbound,
+            reserved_only,
+            reserved_peers: Arc::new(reserved_peers.into_iter().collect()),
+            stats: Arc::new(RwLock::new(HashMap::new())),
+            routes: Arc::new(RwLock::new(HashMap::new())),
+            peer_connections: Arc::new(RwLock::new(HashMap::new())),
+            signing_key: generate_signing_key(),
+            known_keys: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
+    /// Hex-encoded ed25519 public key backing this server's handshake
+    /// signatures, attached to every `Presence` we send.
+    fn public_key_hex(&self) -> String {
+        hex::encode(self.signing_key.verifying_key().to_bytes())
+    }
+
     /// Check if we're already connected to a peer URL
     pub async fn is_connected_to_url(&self, url: &str) -> bool {
         self.connected_urls.read().await.contains(url)
     }
 
+    /// Agent ID -> dialed URL for every peer we've successfully connected
+    /// to outbound. Inbound peers have no URL of ours to report, so they're
+    /// not included; this is the set of addresses we can vouch are dialable.
+    pub async fn peer_urls(&self) -> HashMap<String, String> {
+        self.peer_urls.read().await.clone()
+    }
+
+    /// Snapshot of per-peer traffic tallies (messages/bytes by variant, plus
+    /// connect/disconnect counts and recent ping RTT samples), for periodic
+    /// reporting.
+    pub async fn peer_stats(&self) -> HashMap<String, PeerStats> {
+        self.stats.read().await.clone()
+    }
+
+    /// Record one measured ping round-trip time for `peer_id`, feeding
+    /// `PeerStats::avg_ping`/`med_ping`/`max_ping`.
+    pub async fn record_rtt(&self, peer_id: &str, rtt: Duration) {
+        record_rtt(&self.stats, peer_id, rtt).await;
+    }
+
+    /// Snapshot of when each peer was last heard from, for periodic reporting.
+    pub async fn last_seen_snapshot(&self) -> HashMap<String, Instant> {
+        self.last_seen.read().await.clone()
+    }
+
+    /// Bundle up the shared connection-handling state passed to both the
+    /// axum (TCP) and raw-tungstenite (Unix) accept paths, so they stay in
+    /// sync as fields are added.
+    fn build_app_state(&self) -> AppState {
+        AppState {
+            outgoing: self.outgoing.clone(),
+            agent_id: self.agent_id.clone(),
+            connected_peers: self.connected_peers.clone(),
+            incoming_tx: self.incoming_tx.clone(),
+            gossip_seen: self.gossip_seen.clone(),
+            pending_requests: self.pending_requests.clone(),
+            last_seen: self.last_seen.clone(),
+            inbound_peers: self.inbound_peers.clone(),
+            max_inbound: self.max_inbound,
+            reserved_only: self.reserved_only,
+            reserved_peers: self.reserved_peers.clone(),
+            stats: self.stats.clone(),
+            routes: self.routes.clone(),
+            peer_connections: self.peer_connections.clone(),
+            known_keys: self.known_keys.clone(),
+            signing_key: self.signing_key.clone(),
+            public_key_hex: self.public_key_hex(),
+        }
+    }
+
     pub async fn start(&self) {
-        let tx = self.tx.clone();
         let agent_id = self.agent_id.clone();
-        let connected_peers = self.connected_peers.clone();
-        let incoming_tx = self.incoming_tx.clone();
-
-        let app_state = AppState {
-            tx,
-            agent_id: agent_id.clone(),
-            connected_peers,
-            incoming_tx,
-        };
+        let app_state = self.build_app_state();
 
         let app = Router::new()
             .route("/ws", get(ws_handler))
@@ -128,143 +975,506 @@ impl WebSocketServer {
         });
     }
 
-    /// Connect to a peer and return the result
-    pub async fn connect_to_peer(&self, peer_url: &str) -> PeerConnectionResult {
-        // Skip if already connected to this URL
-        if self.is_connected_to_url(peer_url).await {
-            return PeerConnectionResult::Connected(peer_url.to_string());
+    /// Bind a Unix-domain-socket listener at `path` alongside the TCP
+    /// listener started by [`WebSocketServer::start`], so agents on the
+    /// same host can reach each other without going through the loopback
+    /// TCP stack. Shares the same [`AppState`] and connection bookkeeping
+    /// as inbound TCP peers; only the framing (raw tungstenite instead of
+    /// axum's WebSocket extractor) differs, since axum's `serve` doesn't
+    /// support non-TCP listeners.
+    pub async fn start_unix(&self, path: &str) {
+        // Remove a stale socket file left behind by a previous run; binding
+        // to an existing path otherwise fails with `AddrInUse`.
+        let _ = std::fs::remove_file(path);
+
+        let listener = match UnixListener::bind(path) {
+            Ok(listener) => listener,
+            Err(e) => {
+                output::agent_error(
+                    &self.agent_id,
+                    &format!("Failed to bind unix socket {}: {}", path, e),
+                );
+                return;
+            }
+        };
+        output::agent_success(
+            &self.agent_id,
+            &format!("WebSocket server listening on unix://{}", path),
+        );
+
+        let agent_id = self.agent_id.clone();
+        let app_state = self.build_app_state();
+
+        tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        let state = app_state.clone();
+                        tokio::spawn(async move {
+                            match accept_async(stream).await {
+                                Ok(ws_stream) => handle_unix_socket(ws_stream, state).await,
+                                Err(e) => output::agent_warn(
+                                    &state.agent_id,
+                                    &format!("Unix socket handshake failed: {}", e),
+                                ),
+                            }
+                        });
+                    }
+                    Err(e) => {
+                        output::agent_error(&agent_id, &format!("Unix listener accept error: {}", e));
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    /// Connect to a peer and return the result. `peer_address` is either a
+    /// `ws://host:port/ws` URL or a `unix:///path/to.sock` path; see
+    /// [`PeerAddress`].
+    pub async fn connect_to_peer(&self, peer_address: &str) -> PeerConnectionResult {
+        // Skip if already connected to this address
+        if self.is_connected_to_url(peer_address).await {
+            return PeerConnectionResult::Connected(
+                peer_address.to_string(),
+                ConnectionDirection::Outbound,
+            );
+        }
+
+        let outbound_count = {
+            let connected = self.connected_peers.read().await.len();
+            let inbound = self.inbound_peers.read().await.len();
+            connected.saturating_sub(inbound)
+        };
+        if outbound_count >= self.max_outbound {
+            let err = "Outbound connection limit reached".to_string();
+            output::agent_warn(&self.agent_id, &format!("Not dialing {}: {}", peer_address, err));
+            return PeerConnectionResult::Failed(peer_address.to_string(), err);
         }
 
         let agent_id = self.agent_id.clone();
-        let peer_url_owned = peer_url.to_string();
+        let peer_address_owned = peer_address.to_string();
+
+        match PeerAddress::parse(peer_address) {
+            PeerAddress::Tcp(url) => match timeout(Duration::from_secs(5), connect_async(&url)).await {
+                Ok(Ok((ws_stream, _))) => {
+                    output::peer_event(&agent_id, &format!("Connected to peer: {}", peer_address_owned));
+                    self.complete_outbound_handshake(ws_stream, peer_address_owned)
+                        .await
+                }
+                Ok(Err(e)) => self.dial_failed(peer_address_owned, e.to_string()).await,
+                Err(_) => {
+                    self.dial_failed(peer_address_owned, "Connection timeout".to_string())
+                        .await
+                }
+            },
+            PeerAddress::Unix(path) => {
+                let dial = async {
+                    let stream = UnixStream::connect(&path).await.map_err(|e| e.to_string())?;
+                    client_async("ws://localhost/ws", stream)
+                        .await
+                        .map(|(ws_stream, _)| ws_stream)
+                        .map_err(|e| e.to_string())
+                };
+                match timeout(Duration::from_secs(5), dial).await {
+                    Ok(Ok(ws_stream)) => {
+                        output::peer_event(&agent_id, &format!("Connected to peer: {}", peer_address_owned));
+                        self.complete_outbound_handshake(ws_stream, peer_address_owned)
+                            .await
+                    }
+                    Ok(Err(err)) => self.dial_failed(peer_address_owned, err).await,
+                    Err(_) => {
+                        self.dial_failed(peer_address_owned, "Connection timeout".to_string())
+                            .await
+                    }
+                }
+            }
+        }
+    }
+
+    /// Log and return a `Failed` result for a dial attempt. Doesn't touch the
+    /// outgoing queue registry; [`WebSocketServer::complete_outbound_handshake`]
+    /// unregisters its queue itself before calling this on a failed handshake.
+    async fn dial_failed(&self, peer_address: String, err: String) -> PeerConnectionResult {
+        output::agent_error(
+            &self.agent_id,
+            &format!("Failed to connect to {}: {}", peer_address, err),
+        );
+        PeerConnectionResult::Failed(peer_address, err)
+    }
+
+    /// Run the presence handshake and, on success, spawn the read/write
+    /// tasks that carry ongoing traffic for a freshly-dialed peer. Generic
+    /// over the underlying transport so TCP and Unix-socket dials share one
+    /// implementation past the point the connection is established.
+    async fn complete_outbound_handshake<S>(
+        &self,
+        ws_stream: WebSocketStream<S>,
+        peer_address_owned: String,
+    ) -> PeerConnectionResult
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let agent_id = self.agent_id.clone();
         let connected_peers = self.connected_peers.clone();
         let connected_urls = self.connected_urls.clone();
-        let mut rx = self.tx.subscribe();
+        let peer_urls = self.peer_urls.clone();
+        let outgoing = self.outgoing.clone();
+        let (outgoing_id, mut rx) = outgoing.register().await;
         let incoming_tx = self.incoming_tx.clone();
+        let gossip_seen = self.gossip_seen.clone();
+        let pending_requests = self.pending_requests.clone();
+        let last_seen = self.last_seen.clone();
+        let stats = self.stats.clone();
+        let routes = self.routes.clone();
+        let peer_connections = self.peer_connections.clone();
 
-        // Try to connect with a timeout
-        let connect_result = timeout(Duration::from_secs(5), connect_async(&peer_url_owned)).await;
+        let (mut write, mut read) = ws_stream.split();
+        let signing_key = self.signing_key.clone();
+        let public_key_hex = self.public_key_hex();
+        let known_keys = self.known_keys.clone();
 
-        match connect_result {
-            Ok(Ok((ws_stream, _))) => {
-                output::peer_event(&agent_id, &format!("Connected to peer: {}", peer_url_owned));
-                let (mut write, mut read) = ws_stream.split();
+        // Our own nonce, sent in `Presence` below, that the acceptor must
+        // sign and echo back in `PresenceAck` - proof of the acceptor's
+        // identity, mirroring the proof the acceptor already demands of us
+        // via `Challenge`.
+        let ack_nonce = Uuid::new_v4().to_string();
 
-                // Send presence message
-                let presence = AgentMessage::Presence {
-                    agent_id: agent_id.clone(),
-                };
-                let msg = serde_json::to_string(&presence).unwrap();
-                let _ = write.send(TungsteniteMessage::Text(msg.into())).await;
-
-                // Wait for presence_ack with timeout
-                let ack_result = timeout(Duration::from_secs(3), async {
-                    while let Some(Ok(msg)) = read.next().await {
-                        if let TungsteniteMessage::Text(text) = msg {
-                            if let Ok(parsed) = serde_json::from_str::<AgentMessage>(&text) {
-                                if let AgentMessage::PresenceAck { agent_id: peer_id } = &parsed {
-                                    return Some(peer_id.clone());
-                                }
-                                // Handle other messages
-                                log_peer_message_received(&parsed);
-                                let _ = incoming_tx.send(parsed).await;
+        // Wait for presence_ack with timeout. The acceptor issues a
+        // `Challenge` first; we answer it with a signed `Presence` before
+        // anything is considered connected.
+        let ack_result = timeout(Duration::from_secs(3), async {
+            while let Some(Ok(msg)) = read.next().await {
+                if let TungsteniteMessage::Text(text) = msg {
+                    if let Ok(parsed) = serde_json::from_str::<AgentMessage>(&text) {
+                        // Identity isn't known yet pre-handshake, so the
+                        // message's own sender_id is our best signal here.
+                        last_seen
+                            .write()
+                            .await
+                            .insert(parsed.sender_id().to_string(), Instant::now());
+                        if let AgentMessage::Challenge { nonce } = &parsed {
+                            let presence = AgentMessage::Presence {
+                                agent_id: agent_id.clone(),
+                                public_key: public_key_hex.clone(),
+                                signature: sign_hex(&signing_key, nonce.as_bytes()),
+                                ack_nonce: ack_nonce.clone(),
+                            };
+                            let msg = serde_json::to_string(&presence).unwrap();
+                            let _ = write.send(TungsteniteMessage::Text(msg.into())).await;
+                            continue;
+                        }
+                        if let AgentMessage::PresenceAck {
+                            agent_id: peer_id,
+                            public_key,
+                            signature,
+                        } = &parsed
+                        {
+                            if !verify_presence_signature(public_key, ack_nonce.as_bytes(), signature) {
+                                output::agent_error(
+                                    &agent_id,
+                                    &format!(
+                                        "Rejecting peer {}: handshake ack signature verification failed",
+                                        peer_id
+                                    ),
+                                );
+                                return None;
                             }
+                            if !check_and_pin_key(&known_keys, peer_id, public_key).await {
+                                output::agent_error(
+                                    &agent_id,
+                                    &format!(
+                                        "Rejecting peer {}: public key doesn't match the one we pinned for it",
+                                        peer_id
+                                    ),
+                                );
+                                return None;
+                            }
+                            return Some(peer_id.clone());
                         }
+                        // Handle other messages
+                        process_incoming_message(
+                            parsed,
+                            &outgoing,
+                            &incoming_tx,
+                            &gossip_seen,
+                            &pending_requests,
+                            &agent_id,
+                            &routes,
+                            &peer_connections,
+                            outgoing_id,
+                        )
+                        .await;
                     }
-                    None
-                })
-                .await;
+                }
+            }
+            None
+        })
+        .await;
 
-                match ack_result {
-                    Ok(Some(peer_id)) => {
-                        output::agent_success(
-                            &agent_id,
-                            &format!("ü§ù Handshake complete with peer: {}", peer_id),
-                        );
-                        connected_peers.write().await.insert(peer_id.clone());
-                        connected_urls.write().await.insert(peer_url_owned.clone());
-
-                        // Spawn tasks to handle ongoing communication
-                        let agent_id_recv = agent_id.clone();
-                        let incoming_tx_clone = incoming_tx.clone();
-                        let connected_peers_clone = connected_peers.clone();
-                        let connected_urls_clone = connected_urls.clone();
-                        let peer_url_for_cleanup = peer_url_owned.clone();
-                        tokio::spawn(async move {
-                            while let Some(Ok(msg)) = read.next().await {
-                                if let TungsteniteMessage::Text(text) = msg {
-                                    if let Ok(parsed) = serde_json::from_str::<AgentMessage>(&text)
-                                    {
-                                        log_peer_message_received(&parsed);
-                                        let _ = incoming_tx_clone.send(parsed).await;
-                                    }
-                                }
-                            }
-                            // Peer disconnected - only log if we actually removed them
-                            let was_connected = connected_peers_clone.write().await.remove(&peer_id);
-                            connected_urls_clone.write().await.remove(&peer_url_for_cleanup);
-                            if was_connected {
-                                output::agent_warn(&agent_id_recv, &format!("Peer disconnected: {}", peer_id));
-                            }
-                        });
+        match ack_result {
+            Ok(Some(peer_id)) => {
+                output::agent_success(
+                    &agent_id,
+                    &format!("\u{1f91d} Handshake complete with peer: {}", peer_id),
+                );
+                connected_peers.write().await.insert(peer_id.clone());
+                connected_urls.write().await.insert(peer_address_owned.clone());
+                peer_urls
+                    .write()
+                    .await
+                    .insert(peer_id.clone(), peer_address_owned.clone());
+                peer_connections.write().await.insert(peer_id.clone(), outgoing_id);
+                record_connect(&stats, &peer_id).await;
 
-                        let agent_id_send = agent_id.clone();
-                        tokio::spawn(async move {
-                            while let Ok(msg) = rx.recv().await {
-                                if write
-                                    .send(TungsteniteMessage::Text(msg.clone().into()))
+                // Spawn tasks to handle ongoing communication
+                let agent_id_recv = agent_id.clone();
+                let incoming_tx_clone = incoming_tx.clone();
+                let outgoing_clone = outgoing.clone();
+                let gossip_seen_clone = gossip_seen.clone();
+                let pending_requests_clone = pending_requests.clone();
+                let last_seen_clone = last_seen.clone();
+                let connected_peers_clone = connected_peers.clone();
+                let connected_urls_clone = connected_urls.clone();
+                let peer_urls_clone = peer_urls.clone();
+                let peer_connections_clone = peer_connections.clone();
+                let routes_clone = routes.clone();
+                let outgoing_for_cleanup = outgoing.clone();
+                let peer_address_for_cleanup = peer_address_owned.clone();
+                let peer_id_for_liveness = peer_id.clone();
+                let stats_clone = stats.clone();
+                tokio::spawn(async move {
+                    while let Some(Ok(msg)) = read.next().await {
+                        if let TungsteniteMessage::Text(text) = msg {
+                            if let Ok(parsed) = serde_json::from_str::<AgentMessage>(&text) {
+                                // Track liveness against this connection's
+                                // known peer, not a gossiped message's origin
+                                last_seen_clone
+                                    .write()
                                     .await
-                                    .is_err()
-                                {
-                                    break;
-                                }
-                                log_peer_message_sent(&agent_id_send, &msg);
+                                    .insert(peer_id_for_liveness.clone(), Instant::now());
+                                record_received(&stats_clone, &peer_id_for_liveness, &parsed, text.len())
+                                    .await;
+                                process_incoming_message(
+                                    parsed,
+                                    &outgoing_clone,
+                                    &incoming_tx_clone,
+                                    &gossip_seen_clone,
+                                    &pending_requests_clone,
+                                    &agent_id_recv,
+                                    &routes_clone,
+                                    &peer_connections_clone,
+                                    outgoing_id,
+                                )
+                                .await;
                             }
-                        });
-
-                        PeerConnectionResult::Connected(peer_url_owned)
+                        }
                     }
-                    Ok(None) => {
-                        let err = "Connection closed before handshake".to_string();
-                        output::agent_error(
-                            &agent_id,
-                            &format!("Failed to connect to {}: {}", peer_url_owned, err),
-                        );
-                        PeerConnectionResult::Failed(peer_url_owned, err)
+                    // Peer disconnected - only log if we actually removed them
+                    let was_connected = connected_peers_clone.write().await.remove(&peer_id);
+                    connected_urls_clone.write().await.remove(&peer_address_for_cleanup);
+                    peer_urls_clone.write().await.remove(&peer_id);
+                    peer_connections_clone.write().await.remove(&peer_id);
+                    outgoing_for_cleanup.unregister(outgoing_id).await;
+                    if was_connected {
+                        record_disconnect(&stats_clone, &peer_id).await;
+                        output::agent_warn(&agent_id_recv, &format!("Peer disconnected: {}", peer_id));
                     }
-                    Err(_) => {
-                        let err = "Handshake timeout".to_string();
-                        output::agent_error(
-                            &agent_id,
-                            &format!("Failed to connect to {}: {}", peer_url_owned, err),
-                        );
-                        PeerConnectionResult::Failed(peer_url_owned, err)
+                });
+
+                let agent_id_send = agent_id.clone();
+                tokio::spawn(async move {
+                    while let Some(msg) = rx.recv().await {
+                        if write
+                            .send(TungsteniteMessage::Text(msg.clone().into()))
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                        log_peer_message_sent(&agent_id_send, &msg);
                     }
-                }
+                });
+
+                PeerConnectionResult::Connected(peer_address_owned, ConnectionDirection::Outbound)
             }
-            Ok(Err(e)) => {
-                let err = e.to_string();
-                output::agent_error(
-                    &agent_id,
-                    &format!("Failed to connect to {}: {}", peer_url_owned, err),
-                );
-                PeerConnectionResult::Failed(peer_url_owned, err)
+            Ok(None) => {
+                outgoing.unregister(outgoing_id).await;
+                self.dial_failed(
+                    peer_address_owned,
+                    "Connection closed before handshake".to_string(),
+                )
+                .await
             }
             Err(_) => {
-                let err = "Connection timeout".to_string();
-                output::agent_error(
-                    &agent_id,
-                    &format!("Failed to connect to {}: {}", peer_url_owned, err),
-                );
-                PeerConnectionResult::Failed(peer_url_owned, err)
+                outgoing.unregister(outgoing_id).await;
+                self.dial_failed(peer_address_owned, "Handshake timeout".to_string())
+                    .await
+            }
+        }
+    }
+
+    /// Connect to multiple peers concurrently, bounded by `MAX_CONCURRENT_DIALS`
+    /// in-flight attempts at a time and staggered by `DIAL_STAGGER` so a long
+    /// peer list doesn't flood the network with connection attempts on boot.
+    /// Each attempt's full handshake is bounded by `DIAL_HANDSHAKE_TIMEOUT`.
+    pub async fn connect_to_peers(&self, peer_urls: &[String]) -> Vec<PeerConnectionResult> {
+        let mut pending = FuturesUnordered::new();
+        let mut remaining = peer_urls.iter();
+        let mut results = Vec::with_capacity(peer_urls.len());
+
+        for (i, url) in remaining.by_ref().take(MAX_CONCURRENT_DIALS).enumerate() {
+            pending.push(self.dial_with_timeout(url, DIAL_STAGGER * i as u32));
+        }
+
+        while let Some(result) = pending.next().await {
+            results.push(result);
+            if let Some(url) = remaining.next() {
+                pending.push(self.dial_with_timeout(url, DIAL_STAGGER));
+            }
+        }
+
+        results
+    }
+
+    /// Dial a single peer after waiting `start_delay`, bounding its entire
+    /// handshake (not the delay) with one timeout.
+    async fn dial_with_timeout(&self, peer_url: &str, start_delay: Duration) -> PeerConnectionResult {
+        tokio::time::sleep(start_delay).await;
+        match timeout(DIAL_HANDSHAKE_TIMEOUT, self.connect_to_peer(peer_url)).await {
+            Ok(result) => result,
+            Err(_) => {
+                PeerConnectionResult::Failed(peer_url.to_string(), "Handshake timeout".to_string())
             }
         }
     }
 
     /// Broadcast a message to all connected peers
     pub async fn broadcast(&self, message: AgentMessage) {
+        // Mark our own gossip IDs as seen up front so a forwarded copy that
+        // loops back to us over a cyclic topology is dropped, not redelivered.
+        if let Some(msg_id) = message.gossip_id() {
+            self.gossip_seen.write().await.insert(msg_id.to_string());
+        }
         let msg = serde_json::to_string(&message).unwrap();
-        let _ = self.tx.send(msg);
+
+        let peer_ids: Vec<String> = self.connected_peers.read().await.iter().cloned().collect();
+        for peer_id in &peer_ids {
+            record_sent(&self.stats, peer_id, &message, msg.len()).await;
+        }
+
+        self.outgoing.send(msg).await;
+    }
+
+    /// Send `message` to exactly one directly-connected peer instead of
+    /// broadcasting to everyone, e.g. a `Pong` that must only reach whoever
+    /// sent the `Ping` it answers. Returns `false` if `peer_id` has no live
+    /// connection.
+    pub async fn send_to_peer(&self, peer_id: &str, message: AgentMessage) -> bool {
+        let Some(conn_id) = self.peer_connections.read().await.get(peer_id).cloned() else {
+            return false;
+        };
+        let msg = serde_json::to_string(&message).unwrap();
+        record_sent(&self.stats, peer_id, &message, msg.len()).await;
+        self.outgoing.send_to(conn_id, msg).await
+    }
+
+    /// Send `payload` to `target_agent` and await its correlated `Response`,
+    /// failing with [`RequestError::Timeout`] if none arrives in time.
+    pub async fn request(&self, target_agent: &str, payload: String) -> Result<String, RequestError> {
+        self.request_with_timeout(target_agent, payload, DEFAULT_REQUEST_TIMEOUT)
+            .await
+    }
+
+    /// Like [`WebSocketServer::request`] but with an explicit timeout.
+    pub async fn request_with_timeout(
+        &self,
+        target_agent: &str,
+        payload: String,
+        timeout_dur: Duration,
+    ) -> Result<String, RequestError> {
+        let request_id = self.next_request_id.fetch_add(1, Ordering::SeqCst);
+        let (resp_tx, resp_rx) = oneshot::channel();
+        self.pending_requests
+            .lock()
+            .await
+            .insert(request_id, resp_tx);
+
+        self.broadcast(AgentMessage::Request {
+            request_id,
+            agent_id: self.agent_id.clone(),
+            target_agent: target_agent.to_string(),
+            payload,
+        })
+        .await;
+
+        match timeout(timeout_dur, resp_rx).await {
+            Ok(Ok(Ok(payload))) => Ok(payload),
+            Ok(Ok(Err(message))) => Err(RequestError::Remote(message)),
+            Ok(Err(_)) | Err(_) => {
+                self.pending_requests.lock().await.remove(&request_id);
+                Err(RequestError::Timeout)
+            }
+        }
+    }
+
+    /// Reply to a `Request` with `request_id`, broadcasting a correlated `Response`.
+    pub async fn respond(&self, request_id: u64, payload: String) {
+        self.broadcast(AgentMessage::Response {
+            request_id,
+            agent_id: self.agent_id.clone(),
+            payload: Some(payload),
+            error: None,
+            dest: None,
+            ttl: DEFAULT_GOSSIP_TTL,
+        })
+        .await;
+    }
+
+    /// Reply to a `Request` with `request_id` with a failure, surfacing as
+    /// [`RequestError::Remote`] on the requester side instead of a success.
+    pub async fn respond_error(&self, request_id: u64, message: String) {
+        self.broadcast(AgentMessage::Response {
+            request_id,
+            agent_id: self.agent_id.clone(),
+            payload: None,
+            error: Some(message),
+            dest: None,
+            ttl: DEFAULT_GOSSIP_TTL,
+        })
+        .await;
+    }
+
+    /// Record that `next_hop` (a directly-connected peer) advertised
+    /// `dest_agent_id` in its `PeerList`, so a later message addressed to
+    /// `dest_agent_id` can be routed there even though it isn't a direct
+    /// peer of ours.
+    pub async fn record_route(&self, dest_agent_id: &str, next_hop: &str) {
+        if dest_agent_id == next_hop || dest_agent_id == self.agent_id {
+            return;
+        }
+        self.routes
+            .write()
+            .await
+            .insert(dest_agent_id.to_string(), next_hop.to_string());
+    }
+
+    /// Send `message` (a `Text`, `Number`, or `Response` carrying a `dest`)
+    /// toward its target agent via the routing table, without requiring a
+    /// direct connection to it.
+    pub async fn send_routed(&self, message: AgentMessage) {
+        let Some(dest) = message.dest().map(str::to_string) else {
+            return;
+        };
+        route_onward(
+            message,
+            &dest,
+            &self.outgoing,
+            &self.gossip_seen,
+            &self.routes,
+            &self.peer_connections,
+        )
+        .await;
     }
 
     /// Get the number of connected peers
@@ -286,14 +1496,72 @@ impl WebSocketServer {
     pub async fn get_peer_ids(&self) -> Vec<String> {
         self.connected_peers.read().await.iter().cloned().collect()
     }
+
+    /// Drop any connected peer whose last inbound message is older than
+    /// `timeout`, returning the IDs evicted. Intended to be polled
+    /// periodically so a silently-dead peer doesn't linger forever.
+    pub async fn reap_stale_peers(&self, timeout: Duration) -> Vec<String> {
+        let now = Instant::now();
+        let stale: Vec<String> = self
+            .last_seen
+            .read()
+            .await
+            .iter()
+            .filter(|(_, &last)| now.duration_since(last) > timeout)
+            .map(|(peer_id, _)| peer_id.clone())
+            .collect();
+
+        for peer_id in &stale {
+            self.forget_peer(peer_id).await;
+        }
+
+        stale
+    }
+
+    /// Force a peer's session down, e.g. after it misses too many liveness
+    /// pings. Returns `true` if it was actually connected. This only clears
+    /// our bookkeeping (connected/known-URL sets) rather than closing the
+    /// underlying socket, so the existing retry loop redials it on the next
+    /// tick; the old connection's read task notices the peer is gone and
+    /// cleans itself up independently once its socket actually errors out.
+    pub async fn disconnect_peer(&self, peer_id: &str) -> bool {
+        let was_connected = self.connected_peers.read().await.contains(peer_id);
+        self.forget_peer(peer_id).await;
+        was_connected
+    }
+
+    /// Remove a peer from every piece of bookkeeping that tracks it as
+    /// connected, including freeing up its dialed URL (if any) for redialing.
+    async fn forget_peer(&self, peer_id: &str) {
+        self.connected_peers.write().await.remove(peer_id);
+        self.last_seen.write().await.remove(peer_id);
+        self.inbound_peers.write().await.remove(peer_id);
+        self.peer_connections.write().await.remove(peer_id);
+        if let Some(url) = self.peer_urls.write().await.remove(peer_id) {
+            self.connected_urls.write().await.remove(&url);
+        }
+    }
 }
 
 #[derive(Clone)]
 struct AppState {
-    tx: broadcast::Sender<String>,
+    outgoing: OutgoingHub,
     agent_id: String,
     connected_peers: Arc<RwLock<HashSet<String>>>,
     incoming_tx: mpsc::Sender<AgentMessage>,
+    gossip_seen: Arc<RwLock<SeenSet>>,
+    pending_requests: PendingRequests,
+    last_seen: Arc<RwLock<HashMap<String, Instant>>>,
+    inbound_peers: Arc<RwLock<HashSet<String>>>,
+    max_inbound: usize,
+    reserved_only: bool,
+    reserved_peers: Arc<HashSet<String>>,
+    stats: TrafficStats,
+    routes: Routes,
+    peer_connections: PeerConnections,
+    known_keys: KnownKeys,
+    signing_key: SigningKey,
+    public_key_hex: String,
 }
 
 async fn ws_handler(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
@@ -302,67 +1570,199 @@ async fn ws_handler(ws: WebSocketUpgrade, State(state): State<AppState>) -> Resp
 
 async fn handle_socket(socket: WebSocket, state: AppState) {
     let (sender, receiver) = socket.split();
-    let rx = state.tx.subscribe();
+    let (outgoing_id, rx) = state.outgoing.register().await;
     let agent_id = state.agent_id.clone();
-    let tx = state.tx.clone();
+    let outgoing = state.outgoing.clone();
     let connected_peers = state.connected_peers.clone();
     let incoming_tx = state.incoming_tx.clone();
+    let gossip_seen = state.gossip_seen.clone();
+    let pending_requests = state.pending_requests.clone();
+    let last_seen = state.last_seen.clone();
+    let inbound_peers = state.inbound_peers.clone();
+    let stats = state.stats.clone();
+    let routes = state.routes.clone();
+    let peer_connections = state.peer_connections.clone();
+    let known_keys = state.known_keys.clone();
+    let signing_key = state.signing_key.clone();
+    let public_key_hex = state.public_key_hex.clone();
 
     // Spawn task to handle incoming messages
     let recv_agent_id = agent_id.clone();
+    let outgoing_for_cleanup = outgoing.clone();
     tokio::spawn(handle_incoming(
         receiver,
-        tx,
+        outgoing,
         recv_agent_id,
         connected_peers,
         incoming_tx,
+        gossip_seen,
+        pending_requests,
+        last_seen,
+        outgoing_id,
+        outgoing_for_cleanup,
+        inbound_peers,
+        state.max_inbound,
+        state.reserved_only,
+        state.reserved_peers,
+        stats,
+        routes,
+        peer_connections,
+        known_keys,
+        signing_key,
+        public_key_hex,
     ));
 
     // Spawn task to handle outgoing messages
     tokio::spawn(handle_outgoing(sender, rx, agent_id));
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_incoming(
     mut receiver: SplitStream<WebSocket>,
-    tx: broadcast::Sender<String>,
+    outgoing: OutgoingHub,
     agent_id: String,
     connected_peers: Arc<RwLock<HashSet<String>>>,
     incoming_tx: mpsc::Sender<AgentMessage>,
+    gossip_seen: Arc<RwLock<SeenSet>>,
+    pending_requests: PendingRequests,
+    last_seen: Arc<RwLock<HashMap<String, Instant>>>,
+    outgoing_id: u64,
+    outgoing_for_cleanup: OutgoingHub,
+    inbound_peers: Arc<RwLock<HashSet<String>>>,
+    max_inbound: usize,
+    reserved_only: bool,
+    reserved_peers: Arc<HashSet<String>>,
+    stats: TrafficStats,
+    routes: Routes,
+    peer_connections: PeerConnections,
+    known_keys: KnownKeys,
+    signing_key: SigningKey,
+    public_key_hex: String,
 ) {
     let mut peer_agent_id: Option<String> = None;
 
+    // Issue a fresh, connection-scoped nonce the dialer must sign to prove
+    // it holds the private key behind the `agent_id` it claims in `Presence`.
+    let challenge_nonce = Uuid::new_v4().to_string();
+    let challenge_msg =
+        serde_json::to_string(&AgentMessage::Challenge { nonce: challenge_nonce.clone() }).unwrap();
+    outgoing.send_to(outgoing_id, challenge_msg).await;
+
     while let Some(Ok(msg)) = receiver.next().await {
         if let Message::Text(text) = msg {
             if let Ok(parsed) = serde_json::from_str::<AgentMessage>(&text) {
+                // Track liveness against this connection's known peer (once the
+                // handshake has told us who that is), not a gossiped message's
+                // origin, which may be several hops away.
+                let liveness_id = peer_agent_id
+                    .clone()
+                    .unwrap_or_else(|| parsed.sender_id().to_string());
+                last_seen.write().await.insert(liveness_id.clone(), Instant::now());
+                record_received(&stats, &liveness_id, &parsed, text.len()).await;
                 match &parsed {
-                    AgentMessage::Presence { agent_id: peer_id } => {
+                    AgentMessage::Presence {
+                        agent_id: peer_id,
+                        public_key,
+                        signature,
+                        ack_nonce,
+                    } => {
+                        if !verify_presence_signature(public_key, challenge_nonce.as_bytes(), signature)
+                        {
+                            output::agent_error(
+                                &agent_id,
+                                &format!(
+                                    "Rejecting peer {}: handshake signature verification failed",
+                                    peer_id
+                                ),
+                            );
+                            break;
+                        }
+
+                        if !check_and_pin_key(&known_keys, peer_id, public_key).await {
+                            output::agent_error(
+                                &agent_id,
+                                &format!(
+                                    "Rejecting peer {}: public key doesn't match the one we pinned for it",
+                                    peer_id
+                                ),
+                            );
+                            break;
+                        }
+
+                        if reserved_only && !reserved_peers.contains(peer_id) {
+                            output::agent_warn(
+                                &agent_id,
+                                &format!("Rejecting inbound peer {}: not in reserved list", peer_id),
+                            );
+                            break;
+                        }
+
+                        if inbound_peers.read().await.len() >= max_inbound {
+                            let evictable = inbound_peers
+                                .read()
+                                .await
+                                .iter()
+                                .find(|id| !reserved_peers.contains(*id))
+                                .cloned();
+                            match evictable {
+                                Some(victim) if reserved_peers.contains(peer_id) => {
+                                    inbound_peers.write().await.remove(&victim);
+                                    connected_peers.write().await.remove(&victim);
+                                    last_seen.write().await.remove(&victim);
+                                    peer_connections.write().await.remove(&victim);
+                                    output::agent_warn(
+                                        &agent_id,
+                                        &format!(
+                                            "Evicting inbound peer {} to make room for reserved peer {}",
+                                            victim, peer_id
+                                        ),
+                                    );
+                                }
+                                _ => {
+                                    output::agent_warn(
+                                        &agent_id,
+                                        &format!("Rejecting inbound peer {}: inbound slots full", peer_id),
+                                    );
+                                    break;
+                                }
+                            }
+                        }
+
                         // New peer connected, send ack and track them
                         peer_agent_id = Some(peer_id.clone());
                         connected_peers.write().await.insert(peer_id.clone());
-                        output::peer_event(&agent_id, &format!("Peer joined: {}", peer_id));
+                        inbound_peers.write().await.insert(peer_id.clone());
+                        peer_connections.write().await.insert(peer_id.clone(), outgoing_id);
+                        record_connect(&stats, peer_id).await;
+                        output::peer_event(&agent_id, &format!("Peer joined: {} (inbound)", peer_id));
 
-                        // Send presence ack
+                        // Send presence ack, signed over the dialer's own
+                        // nonce so it can verify our identity too, not just
+                        // the other way around.
                         let ack = AgentMessage::PresenceAck {
                             agent_id: agent_id.clone(),
+                            public_key: public_key_hex.clone(),
+                            signature: sign_hex(&signing_key, ack_nonce.as_bytes()),
                         };
                         let ack_msg = serde_json::to_string(&ack).unwrap();
-                        let _ = tx.send(ack_msg);
-                    }
-                    AgentMessage::Ping {
-                        agent_id: _peer_id,
-                        seq,
-                    } => {
-                        // Respond with pong
-                        let pong = AgentMessage::Pong {
-                            agent_id: agent_id.clone(),
-                            seq: *seq,
-                        };
-                        let pong_msg = serde_json::to_string(&pong).unwrap();
-                        let _ = tx.send(pong_msg);
+                        outgoing.send(ack_msg).await;
                     }
                     _ => {
-                        // Forward to incoming channel for agent to process
-                        let _ = incoming_tx.send(parsed).await;
+                        // Gossiped variants are deduped/re-flooded; others
+                        // (including `Ping`, replied to at the agent layer so
+                        // inbound and outbound connections behave the same) forwarded once
+                        process_incoming_message(
+                            parsed,
+                            &outgoing,
+                            &incoming_tx,
+                            &gossip_seen,
+                            &pending_requests,
+                            &agent_id,
+                            &routes,
+                            &peer_connections,
+                            outgoing_id,
+                        )
+                        .await;
                     }
                 }
             }
@@ -370,9 +1770,13 @@ async fn handle_incoming(
     }
 
     // Peer disconnected - only log if we actually removed them
+    outgoing_for_cleanup.unregister(outgoing_id).await;
     if let Some(peer_id) = peer_agent_id {
         let was_connected = connected_peers.write().await.remove(&peer_id);
+        inbound_peers.write().await.remove(&peer_id);
+        peer_connections.write().await.remove(&peer_id);
         if was_connected {
+            record_disconnect(&stats, &peer_id).await;
             output::agent_warn(&agent_id, &format!("Peer disconnected: {}", peer_id));
         }
     }
@@ -380,18 +1784,15 @@ async fn handle_incoming(
 
 async fn handle_outgoing(
     mut sender: SplitSink<WebSocket, Message>,
-    mut rx: broadcast::Receiver<String>,
+    mut rx: mpsc::UnboundedReceiver<String>,
     agent_id: String,
 ) {
-    // Send our presence first
-    let presence = AgentMessage::Presence {
-        agent_id: agent_id.clone(),
-    };
-    let msg = serde_json::to_string(&presence).unwrap();
-    let _ = sender.send(Message::Text(msg.into())).await;
+    // `handle_incoming` issues the presence `Challenge` for this connection
+    // itself (and the dialer answers it with a signed `Presence`), so there's
+    // nothing to send proactively here.
 
     // Forward broadcast messages
-    while let Ok(msg) = rx.recv().await {
+    while let Some(msg) = rx.recv().await {
         if sender.send(Message::Text(msg.clone().into())).await.is_err() {
             break;
         }
@@ -399,6 +1800,240 @@ async fn handle_outgoing(
     }
 }
 
+/// Unix-socket counterpart to [`handle_socket`]: axum's `serve` only
+/// accepts TCP listeners, so inbound Unix connections are handshaken with
+/// `tokio_tungstenite::accept_async` directly and handled with the raw
+/// tungstenite `Message` type instead of axum's. The connection bookkeeping
+/// (`AppState`) and the `AgentMessage` protocol itself are shared with the
+/// TCP path; only the frame type differs.
+async fn handle_unix_socket(ws_stream: WebSocketStream<UnixStream>, state: AppState) {
+    let (sender, receiver) = ws_stream.split();
+    let (outgoing_id, rx) = state.outgoing.register().await;
+    let agent_id = state.agent_id.clone();
+    let outgoing = state.outgoing.clone();
+    let connected_peers = state.connected_peers.clone();
+    let incoming_tx = state.incoming_tx.clone();
+    let gossip_seen = state.gossip_seen.clone();
+    let pending_requests = state.pending_requests.clone();
+    let last_seen = state.last_seen.clone();
+    let inbound_peers = state.inbound_peers.clone();
+    let stats = state.stats.clone();
+    let routes = state.routes.clone();
+    let peer_connections = state.peer_connections.clone();
+    let known_keys = state.known_keys.clone();
+    let signing_key = state.signing_key.clone();
+    let public_key_hex = state.public_key_hex.clone();
+
+    let recv_agent_id = agent_id.clone();
+    let outgoing_for_cleanup = outgoing.clone();
+    tokio::spawn(handle_incoming_unix(
+        receiver,
+        outgoing,
+        recv_agent_id,
+        connected_peers,
+        incoming_tx,
+        gossip_seen,
+        pending_requests,
+        last_seen,
+        outgoing_id,
+        outgoing_for_cleanup,
+        inbound_peers,
+        state.max_inbound,
+        state.reserved_only,
+        state.reserved_peers,
+        stats,
+        routes,
+        peer_connections,
+        known_keys,
+        signing_key,
+        public_key_hex,
+    ));
+
+    tokio::spawn(handle_outgoing_unix(sender, rx, agent_id));
+}
+
+/// Unix-socket counterpart to [`handle_incoming`]; see that function for the
+/// rationale behind each step, which this mirrors exactly aside from the
+/// frame type.
+#[allow(clippy::too_many_arguments)]
+async fn handle_incoming_unix(
+    mut receiver: SplitStream<WebSocketStream<UnixStream>>,
+    outgoing: OutgoingHub,
+    agent_id: String,
+    connected_peers: Arc<RwLock<HashSet<String>>>,
+    incoming_tx: mpsc::Sender<AgentMessage>,
+    gossip_seen: Arc<RwLock<SeenSet>>,
+    pending_requests: PendingRequests,
+    last_seen: Arc<RwLock<HashMap<String, Instant>>>,
+    outgoing_id: u64,
+    outgoing_for_cleanup: OutgoingHub,
+    inbound_peers: Arc<RwLock<HashSet<String>>>,
+    max_inbound: usize,
+    reserved_only: bool,
+    reserved_peers: Arc<HashSet<String>>,
+    stats: TrafficStats,
+    routes: Routes,
+    peer_connections: PeerConnections,
+    known_keys: KnownKeys,
+    signing_key: SigningKey,
+    public_key_hex: String,
+) {
+    let mut peer_agent_id: Option<String> = None;
+
+    // Issue a fresh, connection-scoped nonce the dialer must sign to prove
+    // it holds the private key behind the `agent_id` it claims in `Presence`.
+    let challenge_nonce = Uuid::new_v4().to_string();
+    let challenge_msg =
+        serde_json::to_string(&AgentMessage::Challenge { nonce: challenge_nonce.clone() }).unwrap();
+    outgoing.send_to(outgoing_id, challenge_msg).await;
+
+    while let Some(Ok(msg)) = receiver.next().await {
+        if let TungsteniteMessage::Text(text) = msg {
+            if let Ok(parsed) = serde_json::from_str::<AgentMessage>(&text) {
+                let liveness_id = peer_agent_id
+                    .clone()
+                    .unwrap_or_else(|| parsed.sender_id().to_string());
+                last_seen.write().await.insert(liveness_id.clone(), Instant::now());
+                record_received(&stats, &liveness_id, &parsed, text.len()).await;
+                match &parsed {
+                    AgentMessage::Presence {
+                        agent_id: peer_id,
+                        public_key,
+                        signature,
+                        ack_nonce,
+                    } => {
+                        if !verify_presence_signature(public_key, challenge_nonce.as_bytes(), signature)
+                        {
+                            output::agent_error(
+                                &agent_id,
+                                &format!(
+                                    "Rejecting peer {}: handshake signature verification failed",
+                                    peer_id
+                                ),
+                            );
+                            break;
+                        }
+
+                        if !check_and_pin_key(&known_keys, peer_id, public_key).await {
+                            output::agent_error(
+                                &agent_id,
+                                &format!(
+                                    "Rejecting peer {}: public key doesn't match the one we pinned for it",
+                                    peer_id
+                                ),
+                            );
+                            break;
+                        }
+
+                        if reserved_only && !reserved_peers.contains(peer_id) {
+                            output::agent_warn(
+                                &agent_id,
+                                &format!("Rejecting inbound peer {}: not in reserved list", peer_id),
+                            );
+                            break;
+                        }
+
+                        if inbound_peers.read().await.len() >= max_inbound {
+                            let evictable = inbound_peers
+                                .read()
+                                .await
+                                .iter()
+                                .find(|id| !reserved_peers.contains(*id))
+                                .cloned();
+                            match evictable {
+                                Some(victim) if reserved_peers.contains(peer_id) => {
+                                    inbound_peers.write().await.remove(&victim);
+                                    connected_peers.write().await.remove(&victim);
+                                    last_seen.write().await.remove(&victim);
+                                    peer_connections.write().await.remove(&victim);
+                                    output::agent_warn(
+                                        &agent_id,
+                                        &format!(
+                                            "Evicting inbound peer {} to make room for reserved peer {}",
+                                            victim, peer_id
+                                        ),
+                                    );
+                                }
+                                _ => {
+                                    output::agent_warn(
+                                        &agent_id,
+                                        &format!("Rejecting inbound peer {}: inbound slots full", peer_id),
+                                    );
+                                    break;
+                                }
+                            }
+                        }
+
+                        peer_agent_id = Some(peer_id.clone());
+                        connected_peers.write().await.insert(peer_id.clone());
+                        inbound_peers.write().await.insert(peer_id.clone());
+                        peer_connections.write().await.insert(peer_id.clone(), outgoing_id);
+                        record_connect(&stats, peer_id).await;
+                        output::peer_event(
+                            &agent_id,
+                            &format!("Peer joined: {} (inbound, unix)", peer_id),
+                        );
+
+                        let ack = AgentMessage::PresenceAck {
+                            agent_id: agent_id.clone(),
+                            public_key: public_key_hex.clone(),
+                            signature: sign_hex(&signing_key, ack_nonce.as_bytes()),
+                        };
+                        let ack_msg = serde_json::to_string(&ack).unwrap();
+                        outgoing.send(ack_msg).await;
+                    }
+                    _ => {
+                        process_incoming_message(
+                            parsed,
+                            &outgoing,
+                            &incoming_tx,
+                            &gossip_seen,
+                            &pending_requests,
+                            &agent_id,
+                            &routes,
+                            &peer_connections,
+                            outgoing_id,
+                        )
+                        .await;
+                    }
+                }
+            }
+        }
+    }
+
+    outgoing_for_cleanup.unregister(outgoing_id).await;
+    if let Some(peer_id) = peer_agent_id {
+        let was_connected = connected_peers.write().await.remove(&peer_id);
+        inbound_peers.write().await.remove(&peer_id);
+        peer_connections.write().await.remove(&peer_id);
+        if was_connected {
+            record_disconnect(&stats, &peer_id).await;
+            output::agent_warn(&agent_id, &format!("Peer disconnected: {}", peer_id));
+        }
+    }
+}
+
+/// Unix-socket counterpart to [`handle_outgoing`].
+async fn handle_outgoing_unix(
+    mut sender: SplitSink<WebSocketStream<UnixStream>, TungsteniteMessage>,
+    mut rx: mpsc::UnboundedReceiver<String>,
+    agent_id: String,
+) {
+    // `handle_incoming_unix` issues the presence `Challenge` for this
+    // connection itself, so there's nothing to send proactively here.
+
+    while let Some(msg) = rx.recv().await {
+        if sender
+            .send(TungsteniteMessage::Text(msg.clone().into()))
+            .await
+            .is_err()
+        {
+            break;
+        }
+        log_peer_message_sent(&agent_id, &msg);
+    }
+}
+
 fn log_peer_message_sent(agent_id: &str, raw: &str) {
     if let Ok(parsed) = serde_json::from_str::<AgentMessage>(raw) {
         match parsed {
@@ -413,10 +2048,10 @@ fn log_peer_message_sent(agent_id: &str, raw: &str) {
 
 fn log_peer_message_received(message: &AgentMessage) {
     match message {
-        AgentMessage::Text { agent_id, content } => {
-            output::peer_recv_text(agent_id, content.trim_matches('"'))
+        AgentMessage::Text { origin, content, .. } => {
+            output::peer_recv_text(origin, content.trim_matches('"'))
         }
-        AgentMessage::Number { agent_id, value } => output::peer_recv_number(agent_id, *value),
+        AgentMessage::Number { origin, value, .. } => output::peer_recv_number(origin, *value),
         _ => {}
     }
 }
@@ -1,4 +1,5 @@
-use std::path::Path;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 use tokio::fs;
 
@@ -6,8 +7,22 @@ use tokio::fs;
 pub enum WriterError {
     #[error("Failed to write file: {0}")]
     Io(#[from] std::io::Error),
+    #[error("Edit range {start}..{end} is out of bounds for a {len}-byte file")]
+    OutOfBounds { start: usize, end: usize, len: usize },
+    #[error("Overlapping edits at byte {start}")]
+    OverlappingEdit { start: usize },
+    #[error("No backup found for {0}")]
+    NoBackup(String),
 }
 
+/// A single byte-range replacement, as used by [`FileWriter::apply_edits`].
+#[derive(Debug, Clone)]
+pub struct Edit {
+    pub range: Range<usize>,
+    pub replacement: String,
+}
+
+#[derive(Clone)]
 pub struct FileWriter {
     workspace: String,
 }
@@ -19,12 +34,7 @@ impl FileWriter {
 
     pub async fn write_file(&self, path: &str, content: &str) -> Result<String, WriterError> {
         let full_path = Path::new(&self.workspace).join(path);
-
-        if let Some(parent) = full_path.parent() {
-            fs::create_dir_all(parent).await?;
-        }
-
-        fs::write(&full_path, content).await?;
+        self.atomic_write(&full_path, content.as_bytes()).await?;
         Ok(full_path.to_string_lossy().to_string())
     }
 
@@ -33,4 +43,73 @@ impl FileWriter {
         let content = fs::read_to_string(&full_path).await?;
         Ok(content)
     }
+
+    /// Apply a set of byte-range replacements to `path` without resending
+    /// the whole file. The prior contents are saved to a sibling `.bak`
+    /// file first (see [`FileWriter::rollback`]), edits are applied in
+    /// descending start order so earlier offsets stay valid as later ones
+    /// are rewritten, and the result is persisted atomically.
+    pub async fn apply_edits(&self, path: &str, edits: &[Edit]) -> Result<String, WriterError> {
+        let full_path = Path::new(&self.workspace).join(path);
+        let mut content = fs::read(&full_path).await?;
+
+        let mut sorted_edits: Vec<&Edit> = edits.iter().collect();
+        sorted_edits.sort_by_key(|edit| std::cmp::Reverse(edit.range.start));
+
+        for edit in &sorted_edits {
+            if edit.range.start > edit.range.end || edit.range.end > content.len() {
+                return Err(WriterError::OutOfBounds {
+                    start: edit.range.start,
+                    end: edit.range.end,
+                    len: content.len(),
+                });
+            }
+        }
+        for pair in sorted_edits.windows(2) {
+            if pair[1].range.end > pair[0].range.start {
+                return Err(WriterError::OverlappingEdit {
+                    start: pair[0].range.start,
+                });
+            }
+        }
+
+        fs::write(Self::sibling_path(&full_path, ".bak"), &content).await?;
+
+        for edit in &sorted_edits {
+            content.splice(edit.range.clone(), edit.replacement.clone().into_bytes());
+        }
+
+        self.atomic_write(&full_path, &content).await?;
+        Ok(full_path.to_string_lossy().to_string())
+    }
+
+    /// Restore `path` from the `.bak` file written by the most recent
+    /// [`FileWriter::apply_edits`] call, e.g. after a `cargo build` that
+    /// followed an edit turns out to fail.
+    pub async fn rollback(&self, path: &str) -> Result<(), WriterError> {
+        let full_path = Path::new(&self.workspace).join(path);
+        let backup_path = Self::sibling_path(&full_path, ".bak");
+        let contents = fs::read(&backup_path)
+            .await
+            .map_err(|_| WriterError::NoBackup(path.to_string()))?;
+        self.atomic_write(&full_path, &contents).await
+    }
+
+    /// Write `contents` to a sibling temp file and `fs::rename` it onto
+    /// `full_path`, so a crash mid-write never leaves a half-written file.
+    async fn atomic_write(&self, full_path: &Path, contents: &[u8]) -> Result<(), WriterError> {
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let temp_path = Self::sibling_path(full_path, ".tmp");
+        fs::write(&temp_path, contents).await?;
+        fs::rename(&temp_path, full_path).await?;
+        Ok(())
+    }
+
+    fn sibling_path(full_path: &Path, suffix: &str) -> PathBuf {
+        let mut sibling = full_path.as_os_str().to_os_string();
+        sibling.push(suffix);
+        PathBuf::from(sibling)
+    }
 }
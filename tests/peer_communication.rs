@@ -40,7 +40,7 @@ async fn test_two_agents_can_communicate() {
     // Agent 1 connects to Agent 2
     let result = server1.connect_to_peer("ws://localhost:19002/ws").await;
     assert!(
-        matches!(result, PeerConnectionResult::Connected(_)),
+        matches!(result, PeerConnectionResult::Connected(_, _)),
         "Agent-1 should connect to Agent-2"
     );
 
@@ -54,10 +54,7 @@ async fn test_two_agents_can_communicate() {
     // Agent 1 sends a number message
     let test_value = 42u64;
     server1
-        .broadcast(AgentMessage::Number {
-            agent_id: "agent-1".to_string(),
-            value: test_value,
-        })
+        .broadcast(AgentMessage::number("agent-1".to_string(), test_value))
         .await;
 
     // Agent 2 should receive the message (skip any presence messages)
@@ -67,8 +64,8 @@ async fn test_two_agents_can_communicate() {
         .expect("Should have a message");
 
     match received {
-        AgentMessage::Number { agent_id, value } => {
-            assert_eq!(agent_id, "agent-1");
+        AgentMessage::Number { origin, value, .. } => {
+            assert_eq!(origin, "agent-1");
             assert_eq!(value, test_value);
         }
         other => panic!("Expected Number message, got {:?}", other),
@@ -77,10 +74,10 @@ async fn test_two_agents_can_communicate() {
     // Agent 2 sends a text message back
     let test_content = "Hello from agent-2!".to_string();
     server2
-        .broadcast(AgentMessage::Text {
-            agent_id: "agent-2".to_string(),
-            content: test_content.clone(),
-        })
+        .broadcast(AgentMessage::text(
+            "agent-2".to_string(),
+            test_content.clone(),
+        ))
         .await;
 
     // Agent 1 should receive the message (skip any presence messages)
@@ -90,8 +87,8 @@ async fn test_two_agents_can_communicate() {
         .expect("Should have a message");
 
     match received {
-        AgentMessage::Text { agent_id, content } => {
-            assert_eq!(agent_id, "agent-2");
+        AgentMessage::Text { origin, content, .. } => {
+            assert_eq!(origin, "agent-2");
             assert_eq!(content, test_content);
         }
         other => panic!("Expected Text message, got {:?}", other),
@@ -140,10 +137,10 @@ async fn test_three_agent_network() {
 
     // Create a chain: 1 -> 2 -> 3
     let result1 = server1.connect_to_peer("ws://localhost:19005/ws").await;
-    assert!(matches!(result1, PeerConnectionResult::Connected(_)));
+    assert!(matches!(result1, PeerConnectionResult::Connected(_, _)));
 
     let result2 = server2.connect_to_peer("ws://localhost:19006/ws").await;
-    assert!(matches!(result2, PeerConnectionResult::Connected(_)));
+    assert!(matches!(result2, PeerConnectionResult::Connected(_, _)));
 
     tokio::time::sleep(Duration::from_millis(200)).await;
 
@@ -181,7 +178,7 @@ async fn test_retry_connects_to_late_peer() {
     // Retry connection - should succeed now
     let result = server1.connect_to_peer("ws://localhost:19008/ws").await;
     assert!(
-        matches!(result, PeerConnectionResult::Connected(_)),
+        matches!(result, PeerConnectionResult::Connected(_, _)),
         "Should connect on retry"
     );
 
@@ -192,3 +189,120 @@ async fn test_retry_connects_to_late_peer() {
 
     println!("Retry mechanism test passed!");
 }
+
+/// Test that `request` gets a correlated response once the peer on the
+/// receiving end actually answers it with `respond`.
+#[tokio::test]
+async fn test_request_response_round_trip() {
+    let server1 = WebSocketServer::new("agent-requester".to_string(), 19009);
+    let server2 = WebSocketServer::new("agent-responder".to_string(), 19010);
+
+    server1.start().await;
+    server2.start().await;
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut rx2 = server2
+        .take_incoming_receiver()
+        .await
+        .expect("Should get receiver for agent-responder");
+
+    let result = server1.connect_to_peer("ws://localhost:19010/ws").await;
+    assert!(matches!(result, PeerConnectionResult::Connected(_, _)));
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // Answer whatever request comes in for agent-responder by echoing the
+    // payload back in upper case.
+    let server2_responder = server2;
+    tokio::spawn(async move {
+        loop {
+            match recv_non_presence(&mut rx2).await {
+                Some(AgentMessage::Request {
+                    request_id, payload, ..
+                }) => {
+                    server2_responder
+                        .respond(request_id, payload.to_uppercase())
+                        .await;
+                }
+                Some(_) => continue,
+                None => break,
+            }
+        }
+    });
+
+    let response = timeout(
+        Duration::from_secs(2),
+        server1.request("agent-responder", "hello".to_string()),
+    )
+    .await
+    .expect("Should receive a response within timeout")
+    .expect("Request should succeed");
+
+    assert_eq!(response, "HELLO");
+
+    println!("Request/response round trip test passed!");
+}
+
+/// Test that the dialer rejects a peer that can't prove the identity it
+/// claims in `PresenceAck` - the listener-side counterpart of impersonation
+/// resistance, since a malicious listener could otherwise tell any outbound
+/// dialer any agent_id it likes.
+#[tokio::test]
+async fn test_dialer_rejects_forged_presence_ack() {
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::accept_async;
+    use tokio_tungstenite::tungstenite::Message as TungsteniteMessage;
+    use futures_util::{SinkExt, StreamExt};
+
+    let listener = TcpListener::bind("127.0.0.1:19011")
+        .await
+        .expect("Should bind fake listener");
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.expect("Should accept connection");
+        let ws_stream = accept_async(stream).await.expect("Should complete ws accept");
+        let (mut write, mut read) = ws_stream.split();
+
+        // Issue a challenge like a real acceptor would.
+        let challenge = serde_json::json!({"type": "challenge", "nonce": "fake-nonce"});
+        let _ = write
+            .send(TungsteniteMessage::Text(challenge.to_string().into()))
+            .await;
+
+        // Wait for the dialer's signed Presence (and its ack_nonce), then
+        // reply with a PresenceAck claiming an identity but carrying an
+        // all-zero public key and signature, which can't verify over the
+        // dialer's ack_nonce.
+        while let Some(Ok(TungsteniteMessage::Text(text))) = read.next().await {
+            if text.contains("\"presence\"") {
+                let forged_ack = serde_json::json!({
+                    "type": "presence_ack",
+                    "agent_id": "agent-responder",
+                    "public_key": "00".repeat(32),
+                    "signature": "00".repeat(64),
+                });
+                let _ = write
+                    .send(TungsteniteMessage::Text(forged_ack.to_string().into()))
+                    .await;
+                break;
+            }
+        }
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let server = WebSocketServer::new("agent-dialer".to_string(), 19012);
+    let result = server.connect_to_peer("ws://127.0.0.1:19011/ws").await;
+
+    assert!(
+        matches!(result, PeerConnectionResult::Failed(_, _)),
+        "Dialer should reject a forged presence ack instead of trusting it"
+    );
+    assert!(
+        !server.has_peers().await,
+        "Dialer should not record the forged peer as connected"
+    );
+
+    println!("Dialer forged-ack rejection test passed!");
+}
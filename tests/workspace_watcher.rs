@@ -0,0 +1,48 @@
+use ferris_lab::watcher::{ChangeKind, Watcher};
+use std::fs;
+use std::time::Duration;
+use tokio::time::timeout;
+
+/// Test that writing a relevant file under a project subdirectory produces
+/// a debounced `WorkspaceChange` tagged with that project, and that an
+/// irrelevant file elsewhere in the workspace does not.
+#[tokio::test]
+async fn test_watcher_reports_relevant_changes() {
+    let workspace = std::env::temp_dir().join(format!(
+        "ferris-lab-watcher-test-{}",
+        std::process::id()
+    ));
+    let project_dir = workspace.join("agent-watched");
+    fs::create_dir_all(project_dir.join("src")).expect("Should create project src dir");
+
+    let (_watcher, mut rx) = Watcher::watch(&workspace).expect("Should start watcher");
+
+    // Give the OS watch a moment to attach before generating events.
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let main_path = project_dir.join("src").join("main.rs");
+    fs::write(&main_path, "fn main() {}").expect("Should write main.rs");
+
+    let change = timeout(Duration::from_secs(2), rx.recv())
+        .await
+        .expect("Should receive a change within timeout")
+        .expect("Channel should still be open");
+
+    assert_eq!(change.agent_id, "agent-watched");
+    assert_eq!(change.path, main_path);
+    assert!(matches!(
+        change.kind,
+        ChangeKind::Created | ChangeKind::Modified
+    ));
+
+    // A file outside any project's `src/**`/`Cargo.toml` shouldn't surface.
+    fs::write(workspace.join("README.md"), "not relevant").expect("Should write README");
+
+    let irrelevant = timeout(Duration::from_millis(400), rx.recv()).await;
+    assert!(
+        irrelevant.is_err(),
+        "Watcher should not report changes outside src/**/Cargo.toml"
+    );
+
+    let _ = fs::remove_dir_all(&workspace);
+}